@@ -0,0 +1,162 @@
+use std::f32;
+use std::sync::Arc;
+
+/// Easing curves shape how a transition moves from start to end. Each takes the fraction of
+/// the transition's duration that has passed and returns how far along the transition should be.
+/// All of them start at 0 and end at 1.
+#[derive(Clone, Default)]
+pub enum Easing {
+    /// Constant speed
+    #[default]
+    Linear,
+
+    /// Eases in and out with the classic 3t^2 - 2t^3 curve
+    SmoothStep,
+
+    /// Starts slow and speeds up
+    CubicIn,
+
+    /// Starts fast and slows down
+    CubicOut,
+
+    /// Starts slow, speeds up through the middle, and slows down at the end
+    CubicInOut,
+
+    /// Starts very slow then shoots off
+    ExponentialIn,
+
+    /// Shoots off then settles in slowly
+    ExponentialOut,
+
+    /// Exponential in for the first half and exponential out for the second
+    ExponentialInOut,
+
+    /// Overshoots and wobbles into place like a damped spring. The damping is how quickly the
+    /// wobble dies out and the frequency is how fast it wobbles, in radians per transition. With
+    /// too little damping it will not have settled by the end, and will snap into place.
+    Spring { damping: f32, frequency: f32 },
+
+    /// Any curve you like, it should map 0 to 0 and 1 to 1
+    Custom(Arc<dyn Fn(f32) -> f32 + Send + Sync>),
+}
+
+impl Easing {
+    /// Wrap a closure as a custom easing
+    pub fn custom<F: Fn(f32) -> f32 + Send + Sync + 'static>(easing: F) -> Easing {
+        Easing::Custom(Arc::new(easing))
+    }
+
+    /// A spring with a little overshoot that has settled by the end of the transition
+    pub fn spring() -> Easing {
+        Easing::Spring {
+            damping: 8.0,
+            frequency: 12.0,
+        }
+    }
+
+    /// Find how far along the transition should be, t is clamped to [0, 1]
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Easing::Linear => t,
+            Easing::SmoothStep => t * t * (3.0 - 2.0 * t),
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::ExponentialIn => exponential_in(t),
+            Easing::ExponentialOut => 1.0 - exponential_in(1.0 - t),
+            Easing::ExponentialInOut => {
+                if t < 0.5 {
+                    exponential_in(2.0 * t) / 2.0
+                } else {
+                    1.0 - exponential_in(2.0 - 2.0 * t) / 2.0
+                }
+            }
+            Easing::Spring { damping, frequency } => {
+                1.0 - (-damping * t).exp() * (frequency * t).cos()
+            }
+            Easing::Custom(ref easing) => easing(t),
+        }
+    }
+}
+
+// The usual 2^(10(t - 1)) curve never quite reaches zero, so it is rescaled to hit 0 and 1 exactly
+fn exponential_in(t: f32) -> f32 {
+    let floor = 2.0f32.powf(-10.0);
+    (2.0f32.powf(10.0 * (t - 1.0)) - floor) / (1.0 - floor)
+}
+
+/// Move a value towards a goal with a critically damped spring, which gets there as fast as
+/// possible without overshooting. The velocity is carried between calls, and the time constant
+/// is roughly how many milliseconds the spring takes to close most of the gap. This is the
+/// closed form approximation from Game Programming Gems 4, so it is stable for any frame time.
+pub fn critically_damped_step(
+    current: f32,
+    goal: f32,
+    velocity: &mut f32,
+    time_constant: f32,
+    elapsed_millis: f32,
+) -> f32 {
+    if time_constant <= 0.0 {
+        *velocity = 0.0;
+        return goal;
+    }
+
+    let omega = 2.0 / time_constant;
+    let x = omega * elapsed_millis;
+    let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+    let change = current - goal;
+    let temp = (*velocity + omega * change) * elapsed_millis;
+    *velocity = (*velocity - omega * temp) * decay;
+    goal + (change + temp) * decay
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert::*;
+
+    #[test]
+    fn test_easing_end_points() {
+        let easings = vec![
+            Easing::Linear,
+            Easing::SmoothStep,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+            Easing::ExponentialIn,
+            Easing::ExponentialOut,
+            Easing::ExponentialInOut,
+            Easing::spring(),
+            Easing::custom(|t| t * t),
+        ];
+        for easing in &easings {
+            close(easing.apply(0.0), 0.0, 1e-6);
+            close(easing.apply(1.0), 1.0, 1e-3);
+        }
+        close(Easing::CubicInOut.apply(0.5), 0.5, 1e-6);
+        close(Easing::ExponentialInOut.apply(0.5), 0.5, 1e-6);
+        assert!(Easing::spring().apply(0.3) > 1.0);
+    }
+
+    #[test]
+    fn test_critically_damped_step_settles_without_overshoot() {
+        let mut value = 0.0;
+        let mut velocity = 0.0;
+        for _ in 0..200 {
+            value = critically_damped_step(value, 1.0, &mut velocity, 100.0, 16.0);
+            assert!(value <= 1.0);
+        }
+        assert!((1.0 - value).abs() < 1e-4);
+
+        // Huge frame times should not blow up
+        let value = critically_damped_step(0.0, 1.0, &mut 0.0, 100.0, 1.0e6);
+        assert!((1.0 - value).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,204 @@
+use cgmath::prelude::*;
+use cgmath::{Matrix3, Vector3};
+use std::f32;
+
+/// An axis aligned bounding box in world coordinates
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Vector3<f32>,
+    pub max: Vector3<f32>,
+}
+
+impl Aabb {
+    pub fn new(min: Vector3<f32>, max: Vector3<f32>) -> Aabb {
+        Aabb { min, max }
+    }
+
+    /// The point in the middle of the box
+    pub fn center(&self) -> Vector3<f32> {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Half of the size of the box along each axis
+    pub fn half_extents(&self) -> Vector3<f32> {
+        (self.max - self.min) * 0.5
+    }
+}
+
+/// A bounding sphere in world coordinates
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sphere {
+    pub center: Vector3<f32>,
+    pub radius: f32,
+}
+
+impl Sphere {
+    pub fn new(center: Vector3<f32>, radius: f32) -> Sphere {
+        Sphere { center, radius }
+    }
+
+    /// The smallest sphere that holds a box
+    pub fn from_aabb(aabb: &Aabb) -> Sphere {
+        Sphere::new(aabb.center(), aabb.half_extents().magnitude())
+    }
+
+    /// The smallest sphere that holds all the points, or None if there are no points. This is
+    /// Welzl's algorithm written as nested loops, with the points shuffled first so it runs in
+    /// expected linear time no matter what order they come in.
+    pub fn from_points(points: &[Vector3<f32>]) -> Option<Sphere> {
+        if points.is_empty() {
+            return None;
+        }
+        let points = shuffled(points);
+
+        let mut sphere = Sphere::new(points[0], 0.0);
+        for i in 1..points.len() {
+            if sphere.holds(points[i]) {
+                continue;
+            }
+            // Point i is outside, so it has to be on the boundary of the new sphere
+            sphere = Sphere::new(points[i], 0.0);
+            for j in 0..i {
+                if sphere.holds(points[j]) {
+                    continue;
+                }
+                sphere = sphere_from_two(points[i], points[j]);
+                for k in 0..j {
+                    if sphere.holds(points[k]) {
+                        continue;
+                    }
+                    sphere = sphere_from_three(points[i], points[j], points[k]);
+                    for l in 0..k {
+                        if !sphere.holds(points[l]) {
+                            sphere = sphere_from_four(points[i], points[j], points[k], points[l]);
+                        }
+                    }
+                }
+            }
+        }
+        Some(sphere)
+    }
+
+    // Contains a point, with a little slack for rounding error
+    fn holds(&self, point: Vector3<f32>) -> bool {
+        (point - self.center).magnitude() <= self.radius * (1.0 + 1.0e-5) + 1.0e-6
+    }
+}
+
+// A deterministic shuffle, we only need to break up any adversarial ordering of the input
+fn shuffled(points: &[Vector3<f32>]) -> Vec<Vector3<f32>> {
+    let mut points = points.to_vec();
+    let mut state: u32 = 0x9E37_79B9;
+    for i in (1..points.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        points.swap(i, state as usize % (i + 1));
+    }
+    points
+}
+
+fn sphere_from_two(a: Vector3<f32>, b: Vector3<f32>) -> Sphere {
+    Sphere::new((a + b) * 0.5, (b - a).magnitude() * 0.5)
+}
+
+// The smallest sphere with all three points on its boundary is centered on their circumcircle
+fn sphere_from_three(a: Vector3<f32>, b: Vector3<f32>, c: Vector3<f32>) -> Sphere {
+    let ab = b - a;
+    let ac = c - a;
+    let normal = ab.cross(ac);
+    let normal_length2 = normal.magnitude2();
+
+    // Points in a line do not have a circumcircle, the two furthest apart are enough
+    if normal_length2 <= f32::EPSILON * ab.magnitude2() * ac.magnitude2() {
+        return largest_of_pairs(&[a, b, c]);
+    }
+
+    let offset = (normal.cross(ab) * ac.magnitude2() + ac.cross(normal) * ab.magnitude2())
+        / (2.0 * normal_length2);
+    Sphere::new(a + offset, offset.magnitude())
+}
+
+// The sphere with all four points on its boundary, found by solving for the point that is the
+// same distance from all of them
+fn sphere_from_four(a: Vector3<f32>, b: Vector3<f32>, c: Vector3<f32>, d: Vector3<f32>) -> Sphere {
+    let rows = Matrix3::from_cols(b - a, c - a, d - a).transpose();
+    let rhs = Vector3::new(
+        (b - a).magnitude2(),
+        (c - a).magnitude2(),
+        (d - a).magnitude2(),
+    ) * 0.5;
+
+    match rows.invert() {
+        Some(inverse) => {
+            let offset = inverse * rhs;
+            Sphere::new(a + offset, offset.magnitude())
+        }
+        // Points in a plane, so the smallest sphere through three of them will have to do
+        None => {
+            let candidates = [
+                sphere_from_three(a, b, c),
+                sphere_from_three(a, b, d),
+                sphere_from_three(a, c, d),
+                sphere_from_three(b, c, d),
+            ];
+            let points = [a, b, c, d];
+            candidates
+                .iter()
+                .filter(|sphere| points.iter().all(|&point| sphere.holds(point)))
+                .fold(None, |smallest: Option<Sphere>, sphere| match smallest {
+                    Some(smallest) if smallest.radius <= sphere.radius => Some(smallest),
+                    _ => Some(*sphere),
+                })
+                .unwrap_or_else(|| largest_of_pairs(&points))
+        }
+    }
+}
+
+fn largest_of_pairs(points: &[Vector3<f32>]) -> Sphere {
+    let mut largest = Sphere::new(points[0], 0.0);
+    for (i, &a) in points.iter().enumerate() {
+        for &b in &points[i + 1..] {
+            let sphere = sphere_from_two(a, b);
+            if sphere.radius > largest.radius {
+                largest = sphere;
+            }
+        }
+    }
+    largest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert::*;
+
+    #[test]
+    fn test_minimal_bounding_sphere() {
+        // The corners of a cube plus some points inside it
+        let offset = Vector3::new(2.0, 0.0, 0.0);
+        let mut points = Vec::new();
+        for i in 0..8 {
+            let corner = |bit| if i & bit == 0 { -1.0 } else { 1.0 };
+            let corner = Vector3::new(corner(1), corner(2), corner(4));
+            points.push(corner + offset);
+            points.push(corner * 0.3 + offset);
+        }
+        let sphere = Sphere::from_points(&points).unwrap();
+        let center: [f32; 3] = sphere.center.into();
+        close(&center, &[2.0, 0.0, 0.0], 1e-4);
+        close(sphere.radius, 3.0f32.sqrt(), 1e-4);
+        assert!(points.iter().all(|&point| sphere.holds(point)));
+
+        // A flat triangle with an obtuse angle only needs its longest edge
+        let triangle = [
+            Vector3::new(-1.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.1, 0.0),
+        ];
+        let sphere = Sphere::from_points(&triangle).unwrap();
+        close(sphere.radius, 1.0, 1e-5);
+
+        assert!(Sphere::from_points(&[]).is_none());
+    }
+}
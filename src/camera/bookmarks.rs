@@ -0,0 +1,82 @@
+use super::{Camera, CameraView};
+
+/// Bookmarks can be numbered, which is handy for binding them to the number keys, or named
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BookmarkKey {
+    Number(u8),
+    Name(String),
+}
+
+impl From<u8> for BookmarkKey {
+    fn from(number: u8) -> BookmarkKey {
+        BookmarkKey::Number(number)
+    }
+}
+
+impl<'a> From<&'a str> for BookmarkKey {
+    fn from(name: &'a str) -> BookmarkKey {
+        BookmarkKey::Name(name.to_string())
+    }
+}
+
+impl From<String> for BookmarkKey {
+    fn from(name: String) -> BookmarkKey {
+        BookmarkKey::Name(name)
+    }
+}
+
+impl Camera {
+    /// Save the current view under a bookmark, replacing whatever was there
+    pub fn save_bookmark<K: Into<BookmarkKey>>(&mut self, key: K) {
+        let view = self.current_view();
+        self.bookmarks.insert(key.into(), view);
+    }
+
+    /// Animate to a bookmarked view. The view we are leaving is pushed onto the view stack, so
+    /// `transition_back` can return to it. Returns false if there is no such bookmark.
+    pub fn recall_bookmark<K: Into<BookmarkKey>>(&mut self, key: K) -> bool {
+        let view = match self.bookmarks.get(&key.into()) {
+            Some(view) => *view,
+            None => return false,
+        };
+        self.push_view();
+        let easing = self.default_easing.clone();
+        let duration = self.default_transition_duration;
+        self.start_view_transition(&view, duration, easing);
+        true
+    }
+
+    /// Remove a bookmark, returning the view it held
+    pub fn delete_bookmark<K: Into<BookmarkKey>>(&mut self, key: K) -> Option<CameraView> {
+        self.bookmarks.remove(&key.into())
+    }
+
+    /// Look up a bookmark without going to it
+    pub fn bookmark<K: Into<BookmarkKey>>(&self, key: K) -> Option<&CameraView> {
+        self.bookmarks.get(&key.into())
+    }
+
+    /// All the bookmarks, numbered ones first in order, then named ones alphabetically
+    pub fn bookmarks(&self) -> impl Iterator<Item = (&BookmarkKey, &CameraView)> {
+        self.bookmarks.iter()
+    }
+
+    /// Push the current view onto the view stack
+    pub fn push_view(&mut self) {
+        let view = self.current_view();
+        self.view_stack.push(view);
+    }
+
+    /// Animate back to the view on top of the view stack. The default view sits at the bottom of
+    /// the stack, so once the stack is empty this goes to the default.
+    pub fn transition_back(&mut self) {
+        let easing = self.default_easing.clone();
+        match self.view_stack.pop() {
+            Some(view) => {
+                let duration = self.default_transition_duration;
+                self.start_view_transition(&view, duration, easing);
+            }
+            None => self.transition_to_default(),
+        }
+    }
+}
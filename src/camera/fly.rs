@@ -0,0 +1,123 @@
+use super::{ButtonState, CamState, Camera};
+use cgmath::prelude::*;
+use cgmath::{Quaternion, Rad, Vector3};
+use std::f32;
+
+/// The directions the camera can move in while flying. Forward and backward follow the view
+/// direction, left and right strafe, and up and down move along the world up axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FlyDirection {
+    Forward,
+    Backward,
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+// Keep the view direction from going all the way to vertical, where yaw stops making sense
+const MAX_FLY_PITCH: f32 = 0.49 * f32::consts::PI;
+
+impl Camera {
+    /// Switch to first person fly controls. The camera keeps its position and orientation, the
+    /// target rides along in front of the camera at the current distance.
+    pub fn enter_fly_mode(&mut self) {
+        self.state = CamState::Fly;
+        self.fly_keys = [false; 6];
+    }
+
+    /// Switch back to the orbit controls. Flying and looking around carry the target along at
+    /// the current distance in front of the camera, so the camera orbits around that point from
+    /// here on and the view does not jump.
+    pub fn exit_fly_mode(&mut self) {
+        if self.state == CamState::Fly {
+            self.state = CamState::Idle;
+        }
+    }
+
+    pub fn toggle_fly_mode(&mut self) {
+        if self.is_flying() {
+            self.exit_fly_mode();
+        } else {
+            self.enter_fly_mode();
+        }
+    }
+
+    pub fn is_flying(&self) -> bool {
+        self.state == CamState::Fly
+    }
+
+    /// Handle the keys that move the camera while flying, the camera keeps moving in a direction
+    /// for as long as it is pressed
+    pub fn handle_fly_key(&mut self, direction: FlyDirection, state: ButtonState) {
+        self.fly_keys[direction as usize] = match state {
+            ButtonState::Pressed => true,
+            ButtonState::Released => false,
+        };
+    }
+
+    /// While boosted, fly speed is multiplied by the fly boost factor. This is usually held down
+    /// with shift.
+    pub fn set_fly_boost(&mut self, boosted: bool) {
+        self.fly_boosted = boosted;
+    }
+
+    /// Handle relative mouse motion in pixels, as opposed to the absolute cursor position in
+    /// `handle_mouse_move`. While flying this turns the camera to look around.
+    pub fn handle_mouse_motion(&mut self, delta_x: f32, delta_y: f32) {
+        if self.state != CamState::Fly {
+            return;
+        }
+
+        let position = self.get_position();
+
+        // Yaw turns about the up axis so the horizon stays level, pitch turns about the camera's
+        // own x-axis and is clamped so we can not flip over
+        let up = self.up.normalize();
+        let forward = self.rotation.rotate_vector(-Vector3::unit_z());
+        let pitch = forward.dot(up).clamp(-1.0, 1.0).asin();
+        let new_pitch =
+            (pitch - delta_y * self.fly_look_sensitivity).clamp(-MAX_FLY_PITCH, MAX_FLY_PITCH);
+        let yaw = Quaternion::from_axis_angle(up, Rad(-delta_x * self.fly_look_sensitivity));
+        let pitch = Quaternion::from_axis_angle(Vector3::unit_x(), Rad(new_pitch - pitch));
+
+        self.rotation = (yaw * self.rotation * pitch).normalize();
+
+        // Looking around should not move the camera, so the target has to swing around it
+        self.target = position
+            - self
+                .rotation
+                .rotate_vector(Vector3::unit_z() * self.distance);
+    }
+
+    // Move the camera according to whichever fly keys are held down
+    pub(super) fn update_fly(&mut self, elapsed_millis: f32) {
+        let mut direction = Vector3::zero();
+        let forward = self.rotation.rotate_vector(-Vector3::unit_z());
+        let right = self.rotation.rotate_vector(Vector3::unit_x());
+        let up = self.up.normalize();
+        let movement = [
+            (FlyDirection::Forward, forward),
+            (FlyDirection::Backward, -forward),
+            (FlyDirection::Left, -right),
+            (FlyDirection::Right, right),
+            (FlyDirection::Up, up),
+            (FlyDirection::Down, -up),
+        ];
+        for &(fly_direction, vector) in &movement {
+            if self.fly_keys[fly_direction as usize] {
+                direction += vector;
+            }
+        }
+
+        if direction.magnitude2() > 0.0 {
+            let boost = if self.fly_boosted {
+                self.fly_boost_factor
+            } else {
+                1.0
+            };
+            let speed = self.fly_speed * boost * (elapsed_millis / 1000.0);
+            self.target += direction.normalize() * speed;
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use super::Camera;
+use animation::Easing;
+use bounds::{Aabb, Sphere};
+use cgmath::Vector3;
+use perspective::ProjectionMode;
+
+impl Camera {
+    /// Animate the camera so the sphere fills the view, keeping the current rotation. The margin
+    /// scales the sphere first, so 1.1 leaves ten percent of room around it. Both the field of
+    /// view and the aspect ratio are taken into account, so the sphere fits whichever way the
+    /// window is narrower.
+    pub fn frame_sphere(&mut self, sphere: &Sphere, margin: f32, easing: Easing) {
+        let radius = sphere.radius * margin;
+        self.push_view();
+
+        let target = sphere.center;
+        let rotation = self.rotation;
+        let duration = self.default_transition_duration;
+        match self.projection_mode {
+            ProjectionMode::Perspective => {
+                // The sphere touches the frustum where the view ray is tangent to it, which is
+                // radius / sin(half angle) away from the center
+                let half_height = self.field_of_view / 2.0;
+                let half_width = (half_height.tan() * self.aspect_ratio).atan();
+                let distance = radius / half_height.min(half_width).sin();
+                self.start_transition_with_easing(target, rotation, distance, duration, easing);
+            }
+            ProjectionMode::Orthographic => {
+                // The extent is half the height, and the width is the extent times the aspect
+                let distance = self.distance;
+                self.start_transition_with_easing(target, rotation, distance, duration, easing);
+                self.transition_end_ortho_extent = radius / self.aspect_ratio.min(1.0);
+            }
+        }
+    }
+
+    /// Animate the camera so the box fills the view, see `frame_sphere`
+    pub fn frame_aabb(&mut self, aabb: &Aabb, margin: f32, easing: Easing) {
+        self.frame_sphere(&Sphere::from_aabb(aabb), margin, easing);
+    }
+
+    /// Animate the camera so all the points fit in the view, see `frame_sphere`. Does nothing if
+    /// there are no points.
+    pub fn frame_points(&mut self, points: &[Vector3<f32>], margin: f32, easing: Easing) {
+        if let Some(sphere) = Sphere::from_points(points) {
+            self.frame_sphere(&sphere, margin, easing);
+        }
+    }
+}
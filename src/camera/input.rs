@@ -0,0 +1,76 @@
+use super::{ButtonState, Camera};
+use input::{Bindings, CameraInput, Key, KeyAction};
+
+impl Camera {
+    /// Handle an input event, with the buttons, keys and characters doing what the bindings say.
+    /// This is what the glutin and winit adapters feed, and it can be driven from any other
+    /// windowing layer or from tests.
+    pub fn handle_input(&mut self, input: CameraInput) {
+        match input {
+            CameraInput::CursorMoved { x, y } => self.handle_mouse_move(x, y),
+            CameraInput::MouseMotion { dx, dy } => self.handle_mouse_motion(dx, dy),
+            CameraInput::MouseButton { button, state } => self.handle_mouse_input(button, state),
+            CameraInput::Scroll { pixel_delta } => self.handle_scroll(pixel_delta),
+            CameraInput::ScrollLines { lines } => self.handle_line_scroll(lines),
+            CameraInput::Key { key, state } => self.handle_key(key, state),
+            CameraInput::Character(c) => self.handle_character(c),
+            CameraInput::Modifiers(modifiers) => self.handle_modifiers(modifiers),
+            CameraInput::Touch { id, phase, x, y } => self.handle_touch(id, phase, x, y),
+        }
+    }
+
+    /// The bindings, for changing them in place
+    pub fn bindings_mut(&mut self) -> &mut Bindings {
+        &mut self.bindings
+    }
+
+    fn handle_key(&mut self, key: Key, state: ButtonState) {
+        if let Some(action) = self.bindings.key_action(key, self.modifiers) {
+            self.perform_key_action(action, state);
+        }
+    }
+
+    fn handle_character(&mut self, c: char) {
+        if let Some(action) = self.bindings.character_action(c) {
+            self.perform_key_action(action, ButtonState::Pressed);
+        }
+    }
+
+    fn perform_key_action(&mut self, action: KeyAction, state: ButtonState) {
+        // The held actions care about both the press and the release
+        match action {
+            KeyAction::Fly(direction) => {
+                self.handle_fly_key(direction, state);
+                return;
+            }
+            KeyAction::ConstrainAxis(constraint) => {
+                match state {
+                    ButtonState::Pressed => self.set_axis_constraint(Some(constraint)),
+                    ButtonState::Released => self.set_axis_constraint(None),
+                }
+                return;
+            }
+            _ => (),
+        }
+
+        // The fly keys overlap with the shortcuts, so they are turned off while flying
+        if state == ButtonState::Released || (self.is_flying() && action != KeyAction::ToggleFly) {
+            return;
+        }
+
+        let easing = self.default_easing.clone();
+        match action {
+            KeyAction::ToggleFly => self.toggle_fly_mode(),
+            KeyAction::SetDefault => self.set_current_as_default(),
+            KeyAction::ResetToDefault => self.transition_to_default(),
+            KeyAction::TransitionBack => self.transition_back(),
+            KeyAction::SaveBookmark(number) => self.save_bookmark(number),
+            KeyAction::RecallBookmark(number) => {
+                self.recall_bookmark(number);
+            }
+            KeyAction::StandardView(view) => self.transition_to_standard_view(view, easing),
+            KeyAction::SnapToNearestView => self.snap_to_nearest_view(easing),
+            KeyAction::Fly(_) | KeyAction::ConstrainAxis(_) => (),
+        }
+    }
+}
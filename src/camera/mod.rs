@@ -1,8 +1,35 @@
+use animation::Easing;
 use cgmath::prelude::*;
 use cgmath::{Basis3, Matrix3, Matrix4, Quaternion, Rad, Vector2, Vector3};
+use frustum::Frustum;
+use input::{Bindings, DragAction, Modifiers};
+use path::CameraPath;
 use perspective;
+use perspective::{ClipSpace, DepthMode, ProjectionMode, YDirection};
+use picking::{Ray, ScreenPoint};
+use std::collections::{BTreeMap, VecDeque};
 use std::f32;
 
+mod bookmarks;
+mod fly;
+mod framing;
+mod input;
+mod momentum;
+mod orbit;
+mod playback;
+mod state;
+mod touch;
+mod view;
+mod zoom;
+
+pub use self::bookmarks::BookmarkKey;
+pub use self::fly::FlyDirection;
+pub use self::playback::PlaybackMode;
+pub use self::state::{CameraState, CAMERA_STATE_VERSION};
+pub use self::touch::TouchPhase;
+pub use self::view::{CameraView, StandardView};
+pub use self::zoom::ZoomMode;
+
 /// The camera is a state machine, what each input does depends on the state that its in.
 /// The possible states are this enum.
 #[derive(PartialEq, Eq)]
@@ -13,21 +40,89 @@ enum CamState {
     /// This mode allows the user to adjust the camera rotation
     Tumble,
 
+    /// This mode zooms as the mouse moves up and down
+    Zoom,
+
+    /// This mode rolls the camera as the mouse circles the middle of the window
+    Roll,
+
+    /// This mode pans, zooms and rolls the camera with two fingers
+    Touch,
+
+    /// This mode adjusts the camera rotation like a turntable, yawing around the up axis and
+    /// pitching towards and away from it without ever rolling
+    Turntable,
+
     /// This state is used for have the camera animate to a destination state
     Transition,
 
     /// This state continues rotation from tumble mode when user lets go
     IdleOrbit,
 
+    /// This mode moves the camera like a first person game, the target rides along with it
+    Fly,
+
+    /// This state plays back a keyframed camera path
+    Path,
+
     /// The camera is neither animating nor using mouse movement
     Idle,
 }
 
+/// How dragging with the mouse rotates the camera around the target
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrbitStyle {
+    /// Free rotation in any direction, including rolling the camera
+    Arcball,
+
+    /// Horizontal drags yaw around the up axis and vertical drags pitch, the horizon stays level
+    Turntable,
+}
+
+/// The virtual trackball mapping decides how a mouse position turns into a point on the
+/// trackball, and how two of those points turn into a rotation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackballMapping {
+    /// Shoemake's arcball, a sphere where everything outside of it is clamped to its edge
+    Shoemake,
+
+    /// Bell's trackball, a sphere that blends into a hyperbolic sheet, rotating by the chord
+    /// between points
+    Bell,
+
+    /// Holroyd's variant of Bell's trackball, which rotates by the angle between points instead
+    Holroyd,
+}
+
+/// An axis that tumbling can be constrained to rotate around, either fixed in the world or
+/// relative to the camera
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AxisConstraint {
+    WorldX,
+    WorldY,
+    WorldZ,
+    CameraX,
+    CameraY,
+    CameraZ,
+}
+
+// The turntable stops just short of looking straight along the up axis, where yaw is undefined
+const MAX_TURNTABLE_PITCH: f32 = 0.49 * f32::consts::PI;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum MouseButton {
     Left,
     Right,
+    Middle,
+    /// The thumb button that usually goes back
+    Back,
+    /// The thumb button that usually goes forward
+    Forward,
+    /// Any other button, numbered by the windowing library
+    Other(u16),
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ButtonState {
     Pressed,
     Released,
@@ -55,22 +150,74 @@ pub struct Camera {
     // Tumble, Pan, and Transition
     original_rotation: Quaternion<f32>,
     original_distance: f32,
+    original_field_of_view: f32,
+    original_ortho_extent: f32,
     original_sphere_point: Vector3<f32>,
+    original_mouse_coords: Vector2<f32>,
 
     // These are for maintaining state with the pan calculations
     original_target: Vector3<f32>,
     original_pan_point: Vector3<f32>,
 
-    // Orbit
+    // Orbit, the velocity is in radians per second about an axis in the camera's frame. After a
+    // turntable drag the orbit carries on as yaw and pitch instead, and the axis holds how the
+    // velocity is split between them in its x and y.
     orbit_enabled: bool,
     orbit_velocity: f32,
-    last_rotation: Quaternion<f32>,
+    orbit_axis: Vector3<f32>,
+    orbit_turntable: bool,
     tumble_duration: f32,
+    tumble_samples: VecDeque<(f32, Quaternion<f32>)>,
+
+    // Smoothing, the zoom goal and progress are in log scale
+    zoom_goal: f32,
+    zoom_progress: f32,
+    zoom_velocity: f32,
+    zoom_pivot: Option<Vector3<f32>>,
+    pan_velocity: Vector3<f32>,
+    pan_duration: f32,
+    pan_samples: VecDeque<(f32, Vector3<f32>)>,
+
+    // Bookmarks and the stack of views that transition_back returns to
+    bookmarks: BTreeMap<BookmarkKey, CameraView>,
+    view_stack: Vec<CameraView>,
+
+    /// What the mouse buttons, keys and characters do in `handle_input` and `handle_mouse_input`,
+    /// see `Bindings` for the presets
+    #[get = "pub"]
+    #[set = "pub"]
+    bindings: Bindings,
+
+    /// The modifier keys that are held, as last reported by `handle_input`
+    #[get = "pub"]
+    modifiers: Modifiers,
+
+    // The mouse button that started the current drag, only letting go of it ends the drag
+    drag_button: Option<MouseButton>,
+
+    // The touch points that are down, by id
+    touches: BTreeMap<u64, Vector2<f32>>,
+
+    // Fly, which keys are held is indexed by FlyDirection
+    fly_keys: [bool; 6],
+    fly_boosted: bool,
+
+    // Path playback, the time is in milliseconds and the direction flips when ping ponging
+    path: Option<CameraPath>,
+    path_time: f32,
+    path_direction: f32,
+    path_paused: bool,
+    path_mode: PlaybackMode,
 
     // Transition
     transition_end_rotation: Quaternion<f32>,
     transition_end_target: Vector3<f32>,
     transition_end_distance: f32,
+    transition_end_field_of_view: f32,
+    transition_end_ortho_extent: f32,
+
+    transition_easing: Easing,
+    transition_dolly_zoom: bool,
 
     // milliseconds
     transition_duration: f32,
@@ -91,6 +238,27 @@ pub struct Camera {
     #[set = "pub"]
     far: f32,
 
+    /// Whether the camera uses a perspective or an orthographic projection
+    #[get = "pub"]
+    projection_mode: ProjectionMode,
+
+    /// Half of the height of the orthographic view volume in world coordinates. This plays the
+    /// role of distance when zooming in orthographic mode.
+    #[get = "pub"]
+    #[set = "pub"]
+    ortho_extent: f32,
+
+    /// How depth is mapped into clip space, this lets you opt into reversed depth and an
+    /// infinitely distant far plane
+    #[get = "pub"]
+    #[set = "pub"]
+    depth_mode: DepthMode,
+
+    /// The clip space convention of the graphics backend the camera is rendering for
+    #[get = "pub"]
+    #[set = "pub"]
+    clip_space: ClipSpace,
+
     /// The field of view to use when making the perspective transform
     #[get = "pub"]
     #[set = "pub"]
@@ -107,6 +275,110 @@ pub struct Camera {
     #[set = "pub"]
     scroll_modifier: f32,
 
+    /// The factor applied to the number of lines from each line based scroll event, by default
+    /// one line zooms about as much as 20 pixels
+    #[get = "pub"]
+    #[set = "pub"]
+    line_scroll_modifier: f32,
+
+    /// How fast the camera moves while flying in world units per second
+    #[get = "pub"]
+    #[set = "pub"]
+    fly_speed: f32,
+
+    /// The fly speed is multiplied by this while the fly boost is held
+    #[get = "pub"]
+    #[set = "pub"]
+    fly_boost_factor: f32,
+
+    /// How far the camera turns while flying in radians per pixel of mouse motion
+    #[get = "pub"]
+    #[set = "pub"]
+    fly_look_sensitivity: f32,
+
+    /// Whether dragging tumbles the camera freely or like a turntable
+    #[get = "pub"]
+    #[set = "pub"]
+    orbit_style: OrbitStyle,
+
+    /// How mouse positions map onto the virtual trackball when tumbling
+    #[get = "pub"]
+    #[set = "pub"]
+    trackball_mapping: TrackballMapping,
+
+    /// While set, tumbling only rotates around this axis
+    #[get = "pub"]
+    axis_constraint: Option<AxisConstraint>,
+
+    /// The world up direction, the turntable yaws around it and fly mode keeps it level
+    #[get = "pub"]
+    #[set = "pub"]
+    up: Vector3<f32>,
+
+    /// How far the turntable turns in radians per unit of screen space dragged
+    #[get = "pub"]
+    #[set = "pub"]
+    turntable_sensitivity: f32,
+
+    /// The time constant of the orbit slowing down in milliseconds, after this long the orbit
+    /// speed has dropped to about a third. Zero means the orbit never slows down.
+    #[get = "pub"]
+    #[set = "pub"]
+    orbit_damping: f32,
+
+    /// The orbit stops once it is slower than this many radians per second
+    #[get = "pub"]
+    #[set = "pub"]
+    orbit_stop_speed: f32,
+
+    /// How many milliseconds at the end of a tumble or pan are used to measure how fast it was
+    /// going when it was let go
+    #[get = "pub"]
+    #[set = "pub"]
+    orbit_sample_window: f32,
+
+    /// With a time constant in milliseconds, scrolling sets a goal that the zoom approaches
+    /// smoothly with a critically damped spring. Zero means scrolling zooms instantly.
+    #[get = "pub"]
+    #[set = "pub"]
+    zoom_time_constant: f32,
+
+    /// What zooming changes in perspective mode
+    #[get = "pub"]
+    #[set = "pub"]
+    zoom_mode: ZoomMode,
+
+    /// The narrowest field of view that zooming goes to
+    #[get = "pub"]
+    #[set = "pub"]
+    min_field_of_view: f32,
+
+    /// The widest field of view that zooming goes to
+    #[get = "pub"]
+    #[set = "pub"]
+    max_field_of_view: f32,
+
+    /// Whether scrolling zooms toward the point under the mouse instead of toward the target
+    #[get = "pub"]
+    #[set = "pub"]
+    zoom_to_cursor: bool,
+
+    /// With a time constant in milliseconds, letting go of a pan keeps the target moving with a
+    /// momentum that decays over time. Zero means the pan stops dead.
+    #[get = "pub"]
+    #[set = "pub"]
+    pan_time_constant: f32,
+
+    /// How fast paths play back, 2 is double speed and negative speeds play backwards
+    #[get = "pub"]
+    #[set = "pub"]
+    path_speed: f32,
+
+    /// The easing that the built in shortcuts and `start_transition` use for their transitions
+    #[get = "pub"]
+    #[set = "pub"]
+    default_easing: Easing,
+
     /// The target is where the camera points in world coordinates
     #[get = "pub"]
     #[set = "pub"]
@@ -131,20 +403,70 @@ impl Camera {
             original_rotation: Quaternion::one(),
             original_target: Vector3::zero(),
             original_distance: 1.0,
+            original_field_of_view: f32::consts::PI / 2.0,
+            original_ortho_extent: 50.0,
 
             // These are the
             original_sphere_point: Vector3::zero(),
+            original_mouse_coords: Vector2::zero(),
             original_pan_point: Vector3::zero(),
 
             // OrbitDelta
             orbit_velocity: 0.0,
             orbit_enabled: false,
-            last_rotation: Quaternion::one(),
+            orbit_axis: Vector3::unit_y(),
+            orbit_turntable: false,
             tumble_duration: 1.0,
+            tumble_samples: VecDeque::new(),
+            orbit_damping: 1500.0,
+            orbit_stop_speed: 0.05,
+            orbit_sample_window: 100.0,
+
+            zoom_goal: 0.0,
+            zoom_progress: 0.0,
+            zoom_pivot: None,
+            zoom_velocity: 0.0,
+            zoom_time_constant: 0.0,
+            pan_velocity: Vector3::zero(),
+            pan_duration: 0.0,
+            pan_samples: VecDeque::new(),
+            zoom_mode: ZoomMode::Dolly,
+            min_field_of_view: f32::consts::PI / 36.0,
+            max_field_of_view: f32::consts::PI * 5.0 / 6.0,
+            zoom_to_cursor: false,
+            pan_time_constant: 0.0,
+
+            bookmarks: BTreeMap::new(),
+            view_stack: Vec::new(),
+
+            fly_keys: [false; 6],
+            bindings: Bindings::aperture(),
+            modifiers: Modifiers::default(),
+            drag_button: None,
+            touches: BTreeMap::new(),
+            fly_boosted: false,
+            path: None,
+            path_time: 0.0,
+            path_direction: 1.0,
+            path_paused: false,
+            path_mode: PlaybackMode::Once,
+            fly_speed: 10.0,
+            fly_boost_factor: 4.0,
+            fly_look_sensitivity: 0.003,
+
+            orbit_style: OrbitStyle::Arcball,
+            axis_constraint: None,
+            trackball_mapping: TrackballMapping::Shoemake,
+            up: Vector3::unit_y(),
+            turntable_sensitivity: f32::consts::PI,
 
             transition_end_rotation: Quaternion::one(),
             transition_end_target: Vector3::zero(),
             transition_end_distance: 1.0,
+            transition_end_field_of_view: f32::consts::PI / 2.0,
+            transition_end_ortho_extent: 50.0,
+            transition_easing: Easing::Linear,
+            transition_dolly_zoom: false,
             transition_duration: Default::default(),
             transition_completed: Default::default(),
 
@@ -153,9 +475,16 @@ impl Camera {
             window_height: 1.0,
             aspect_ratio: 1.0,
             field_of_view: f32::consts::PI / 2.0,
+            projection_mode: ProjectionMode::Perspective,
+            ortho_extent: 50.0,
+            depth_mode: DepthMode::Standard,
+            clip_space: ClipSpace::opengl(),
             near: 0.01,
             far: 1000.0,
             scroll_modifier: 1.0 / 200.0,
+            line_scroll_modifier: 1.0 / 10.0,
+            path_speed: 1.0,
+            default_easing: Easing::Linear,
         }
     }
 
@@ -177,59 +506,111 @@ impl Camera {
                     self.rotation = self.transition_end_rotation;
                     self.target = self.transition_end_target;
                     self.distance = self.transition_end_distance;
+                    self.field_of_view = self.transition_end_field_of_view;
+                    self.ortho_extent = self.transition_end_ortho_extent;
                 } else {
-                    let t = self.transition_completed / self.transition_duration;
+                    let t = self
+                        .transition_easing
+                        .apply(self.transition_completed / self.transition_duration);
 
                     self.target = (1.0 - t) * self.original_target + t * self.transition_end_target;
                     self.distance =
                         (1.0 - t) * self.original_distance + t * self.transition_end_distance;
+                    self.field_of_view = (1.0 - t) * self.original_field_of_view
+                        + t * self.transition_end_field_of_view;
+                    self.ortho_extent = (1.0 - t) * self.original_ortho_extent
+                        + t * self.transition_end_ortho_extent;
                     self.rotation = self
                         .original_rotation
                         .slerp(self.transition_end_rotation, t);
+                    self.hold_dolly_zoom_size();
                 }
             }
             CamState::IdleOrbit => {
-                let mut current_angle = self.rotation.s.acos() * 2.0;
-                current_angle += (elapsed_millis / 1000.0) * self.orbit_velocity;
-                self.rotation.s = (current_angle / 2.0).cos();
+                self.update_orbit(elapsed_millis);
             }
-            CamState::Tumble => {
+            CamState::Tumble | CamState::Turntable => {
                 self.tumble_duration += elapsed_millis;
             }
+            CamState::Pan => {
+                self.pan_duration += elapsed_millis;
+            }
+            CamState::Fly => {
+                self.update_fly(elapsed_millis);
+            }
+            CamState::Path => {
+                self.update_path(elapsed_millis);
+            }
             _ => (),
         }
+
+        if self.state != CamState::Transition {
+            self.update_zoom_smoothing(elapsed_millis);
+            self.update_pan_momentum(elapsed_millis);
+        }
     }
 
-    /// Use this to setup a camera transition
+    /// Use this to setup a camera transition, it moves with the default easing
     pub fn start_transition(
         &mut self,
         end_target: Vector3<f32>,
         end_rotation: Quaternion<f32>,
         end_distance: f32,
         transition_duration: f32,
+    ) {
+        let easing = self.default_easing.clone();
+        self.start_transition_with_easing(
+            end_target,
+            end_rotation,
+            end_distance,
+            transition_duration,
+            easing,
+        );
+    }
+
+    /// Setup a camera transition, the easing shapes how the camera moves from where it is to the
+    /// end of the transition
+    pub fn start_transition_with_easing(
+        &mut self,
+        end_target: Vector3<f32>,
+        end_rotation: Quaternion<f32>,
+        end_distance: f32,
+        transition_duration: f32,
+        easing: Easing,
     ) {
         self.state = CamState::Transition;
+        self.stop_zoom_smoothing();
+        self.pan_velocity = Vector3::zero();
 
         self.original_target = self.target;
         self.original_rotation = self.rotation;
         self.original_distance = self.distance;
+        self.original_field_of_view = self.field_of_view;
+        self.original_ortho_extent = self.ortho_extent;
 
         self.transition_end_target = end_target;
         self.transition_end_rotation = end_rotation;
         self.transition_end_distance = end_distance;
+        self.transition_end_field_of_view = self.field_of_view;
+        self.transition_end_ortho_extent = self.ortho_extent;
         self.transition_duration = transition_duration;
         self.transition_completed = 0.0;
+        self.transition_easing = easing;
+        self.transition_dolly_zoom = false;
     }
 
     // TODO, transitions should be moved to another module I think.
-    // could have state stack, with default at bottom,
-    // could also have snap to axis and thigs like that
     pub fn transition_to_default(&mut self) {
+        let easing = self.default_easing.clone();
+        self.transition_to_default_with_easing(easing);
+    }
+
+    pub fn transition_to_default_with_easing(&mut self, easing: Easing) {
         let rotation = self.default_rotation.clone();
         let target = self.default_target.clone();
         let distance = self.default_distance.clone();
         let duration = self.default_transition_duration;
-        self.start_transition(target, rotation, distance, duration);
+        self.start_transition_with_easing(target, rotation, distance, duration, easing);
     }
 
     pub fn set_current_as_default(&mut self) {
@@ -238,6 +619,18 @@ impl Camera {
         self.default_target = self.target;
     }
 
+    /// Switch between perspective and orthographic projection. When switching, the orthographic
+    /// extent is matched to what the perspective frustum shows at the target, so the size of
+    /// the target on screen does not jump.
+    pub fn set_projection_mode(&mut self, projection_mode: ProjectionMode) {
+        if projection_mode == ProjectionMode::Orthographic
+            && self.projection_mode == ProjectionMode::Perspective
+        {
+            self.ortho_extent = self.distance * (self.field_of_view / 2.0).tan();
+        }
+        self.projection_mode = projection_mode;
+    }
+
     /// Get the position of the camera in world coordinates
     pub fn get_position(&self) -> Vector3<f32> {
         self.target
@@ -260,15 +653,92 @@ impl Camera {
 
         let rotation_transform = Matrix3::from(self.get_rotation().invert());
 
-        let perspective_transform = perspective::fov_perspective_transform(
-            self.field_of_view,
-            self.aspect_ratio,
-            self.near,
-            self.far,
-        );
+        let projection_transform = match self.projection_mode {
+            ProjectionMode::Perspective => perspective::fov_depth_mode_perspective_transform(
+                self.clip_space,
+                self.depth_mode,
+                self.field_of_view,
+                self.aspect_ratio,
+                self.near,
+                self.far,
+            ),
+            ProjectionMode::Orthographic => perspective::extent_depth_mode_orthographic_transform(
+                self.clip_space,
+                self.depth_mode,
+                self.ortho_extent,
+                self.aspect_ratio,
+                self.near,
+                self.far,
+            ),
+        };
 
         // We need to an inverted order of operations becuase the matrix is inverted(?)
-        perspective_transform * Matrix4::from(rotation_transform) * pos_transform
+        projection_transform * Matrix4::from(rotation_transform) * pos_transform
+    }
+
+    /// Get the viewing frustum in world coordinates, for culling things that are off screen
+    pub fn get_frustum(&self) -> Frustum {
+        Frustum::from_camera(self)
+    }
+
+    /// Turn a pixel coordinate, with the origin in the top left of the window, into a world space
+    /// ray heading out of the camera. This is what you want for picking objects with the mouse.
+    pub fn pixel_to_ray(&self, pixel_x: f32, pixel_y: f32) -> Ray {
+        let ndc = self.pixel_to_ndc(Vector2::new(pixel_x, pixel_y));
+        let (near_depth, far_depth) = self.clip_space.near_far_depths(self.depth_mode);
+
+        // The clipspace transform is always invertible as long as the window and frustum are not
+        // degenerate, so we fall back to looking straight ahead just in case
+        Ray::unproject(self.get_clipspace_transform(), ndc, near_depth, far_depth).unwrap_or(Ray {
+            origin: self.get_position(),
+            direction: self.rotation.rotate_vector(-Vector3::unit_z()),
+        })
+    }
+
+    /// Find where a world space point shows up in the window. The pixel coordinates have the
+    /// origin in the top left of the window, the same as `handle_mouse_move`, and they are found
+    /// with the transform from `get_clipspace_transform` so they line up with what is rendered.
+    pub fn world_to_pixel(&self, point: Vector3<f32>) -> ScreenPoint {
+        let clip = self.get_clipspace_transform() * point.extend(1.0);
+        let ndc = clip.truncate() / clip.w;
+
+        // With a perspective projection w is the distance in front of the camera, but an
+        // orthographic projection always has w = 1 so we need to check the view direction
+        let in_front = match self.projection_mode {
+            ProjectionMode::Perspective => clip.w > 0.0,
+            ProjectionMode::Orthographic => {
+                let forward = self.rotation.rotate_vector(-Vector3::unit_z());
+                (point - self.get_position()).dot(forward) > 0.0
+            }
+        };
+
+        ScreenPoint {
+            pixel: self.ndc_to_pixel(ndc.truncate()),
+            depth: ndc.z,
+            in_front,
+        }
+    }
+
+    // Normalized device coordinates cover the whole window with [-1, 1] on both axes, unlike
+    // screen space which keeps the unit circle round. Which way y points depends on the clip space.
+    fn pixel_to_ndc(&self, pixel: Vector2<f32>) -> Vector2<f32> {
+        let x = (2.0 * pixel.x / self.window_width) - 1.0;
+        let y = 1.0 - (2.0 * pixel.y / self.window_height);
+        match self.clip_space.y_direction {
+            YDirection::Up => Vector2::new(x, y),
+            YDirection::Down => Vector2::new(x, -y),
+        }
+    }
+
+    fn ndc_to_pixel(&self, ndc: Vector2<f32>) -> Vector2<f32> {
+        let y = match self.clip_space.y_direction {
+            YDirection::Up => ndc.y,
+            YDirection::Down => -ndc.y,
+        };
+        Vector2::new(
+            (ndc.x + 1.0) * self.window_width / 2.0,
+            (1.0 - y) * self.window_height / 2.0,
+        )
     }
 
     // When dealing with mouse input we need to translate the pixel location into
@@ -306,21 +776,82 @@ impl Camera {
     fn mouse_to_sphere_point(&self, mouse_coords: Vector2<f32>) -> Vector3<f32> {
         let screen_point = self.mouse_to_screen(mouse_coords);
 
-        // Now we find point on sphere by clamping to unit circle
-        // and finding z component
         let screen_point_radius_squared = screen_point.magnitude2();
-        let sphere_point = if screen_point_radius_squared >= 1.0 {
-            // Points on, or mapped to, the circle itself have no z component
-            (screen_point / screen_point_radius_squared.sqrt()).extend(0.0)
-        } else {
-            // Points in the circle get "pushed onto" the sphere
-            // The rotation axis extends into the screen, hence the negative
-            screen_point.extend(-(1.0 - screen_point_radius_squared).sqrt())
+        let sphere_point = match self.trackball_mapping {
+            TrackballMapping::Shoemake => {
+                // Now we find point on sphere by clamping to unit circle
+                // and finding z component
+                if screen_point_radius_squared >= 1.0 {
+                    // Points on, or mapped to, the circle itself have no z component
+                    (screen_point / screen_point_radius_squared.sqrt()).extend(0.0)
+                } else {
+                    // Points in the circle get "pushed onto" the sphere
+                    // The rotation axis extends into the screen, hence the negative
+                    screen_point.extend(-(1.0 - screen_point_radius_squared).sqrt())
+                }
+            }
+            TrackballMapping::Bell | TrackballMapping::Holroyd => {
+                // Near the center we use the sphere, further out we switch to the hyperbolic
+                // sheet z = 1 / (2 * radius), which meets the sphere smoothly at radius 1 / sqrt(2)
+                // and never runs out, so there is no hard edge to clamp to
+                if screen_point_radius_squared <= 0.5 {
+                    screen_point.extend(-(1.0 - screen_point_radius_squared).sqrt())
+                } else {
+                    screen_point.extend(-0.5 / screen_point_radius_squared.sqrt())
+                }
+            }
         };
 
-        // If we were contraining axis, that would go here
+        match self.constraint_sphere_axis() {
+            Some(axis) => constrain_to_axis(sphere_point, axis),
+            None => sphere_point,
+        }
+    }
+
+    // Find the rotation that takes one trackball point to another. Each mapping rotates through
+    // the arc that was actually dragged, rather than twice that like the classic arcball
+    // quaternion built directly from the dot and cross products.
+    fn trackball_rotation(&self, from: Vector3<f32>, to: Vector3<f32>) -> Quaternion<f32> {
+        let axis = from.cross(to);
+        if axis.magnitude2() <= f32::EPSILON * f32::EPSILON {
+            return Quaternion::one();
+        }
 
-        sphere_point
+        let angle = match self.trackball_mapping {
+            // Points on the sphere, or the sheet, are rotated by the angle between them
+            TrackballMapping::Shoemake | TrackballMapping::Holroyd => {
+                from.normalize().dot(to.normalize()).clamp(-1.0, 1.0).acos()
+            }
+            // Bell's trackball uses the length of the chord between the points, which for points
+            // on the sphere is the same as the angle, but keeps growing out on the sheet until it
+            // reaches half a turn
+            TrackballMapping::Bell => 2.0 * ((to - from).magnitude() / 2.0).min(1.0).asin(),
+        };
+        Quaternion::from_axis_angle(axis.normalize(), Rad(angle))
+    }
+
+    // The arcball rotation is applied in the camera's frame as it was when the tumble started, so
+    // that is the frame the constraint axis has to be expressed in
+    fn constraint_sphere_axis(&self) -> Option<Vector3<f32>> {
+        let to_camera = self.original_rotation.invert();
+        self.axis_constraint.map(|constraint| match constraint {
+            AxisConstraint::WorldX => to_camera.rotate_vector(Vector3::unit_x()),
+            AxisConstraint::WorldY => to_camera.rotate_vector(Vector3::unit_y()),
+            AxisConstraint::WorldZ => to_camera.rotate_vector(Vector3::unit_z()),
+            AxisConstraint::CameraX => Vector3::unit_x(),
+            AxisConstraint::CameraY => Vector3::unit_y(),
+            AxisConstraint::CameraZ => Vector3::unit_z(),
+        })
+    }
+
+    /// Constrain tumbling to a single axis, or pass None to rotate freely again. This can be
+    /// changed in the middle of a tumble, the rotation so far is kept.
+    pub fn set_axis_constraint(&mut self, axis_constraint: Option<AxisConstraint>) {
+        self.axis_constraint = axis_constraint;
+        if self.state == CamState::Tumble {
+            self.original_rotation = self.rotation;
+            self.original_sphere_point = self.mouse_to_sphere_point(self.prev_mouse_coords);
+        }
     }
 
     // When panning we want to the mouse to act like it was dragging the camera target around
@@ -329,6 +860,17 @@ impl Camera {
     fn mouse_to_pan_point(&self, mouse_coords: Vector2<f32>) -> Vector3<f32> {
         let screen_point = self.mouse_to_screen(mouse_coords);
 
+        // In orthographic mode there is no perspective scaling, the view volume is
+        // 2 * ortho_extent world units tall no matter how far away the plane is. Screen space
+        // is scaled by the smaller window dimension, so we undo that to get exactly one world
+        // unit of pan per world unit of projected movement.
+        if self.projection_mode == ProjectionMode::Orthographic {
+            let pixel_radius = self.window_width.min(self.window_height) / 2.0;
+            let world_per_pixel = (2.0 * self.ortho_extent) / self.window_height;
+            let plane_point = (screen_point * pixel_radius * world_per_pixel).extend(self.distance);
+            return Matrix3::from(self.get_rotation()) * plane_point;
+        }
+
         // Using similiar triangles we can scale the screen point onto a plane camera.distance away
         // since we know that the "distance" to the screen plane is defined by the near attribute
         // of our viewing frustrum
@@ -346,8 +888,39 @@ impl Camera {
         pan_point
     }
 
+    // The turntable works in terms of yaw around the up axis and pitch away from the horizontal
+    // plane. We find the yaw and pitch of where we started, then add on the mouse movement.
+    fn turntable_rotation(&self, mouse_coords: Vector2<f32>) -> Quaternion<f32> {
+        let screen_delta =
+            self.mouse_to_screen(mouse_coords) - self.mouse_to_screen(self.original_mouse_coords);
+
+        let (yaw, pitch) = self.turntable_angles(self.original_rotation);
+        self.turntable_from_angles(
+            yaw - screen_delta.x * self.turntable_sensitivity,
+            pitch - screen_delta.y * self.turntable_sensitivity,
+        )
+    }
+
+    // Work in a frame where the up axis is y, then the camera's backwards direction gives us
+    // yaw and pitch directly
+    fn turntable_angles(&self, rotation: Quaternion<f32>) -> (f32, f32) {
+        let up_frame = Quaternion::from_arc(Vector3::unit_y(), self.up.normalize(), None);
+        let back = up_frame
+            .invert()
+            .rotate_vector(rotation.rotate_vector(Vector3::unit_z()));
+        (back.x.atan2(back.z), back.y.clamp(-1.0, 1.0).asin())
+    }
+
+    // The rotation for a yaw and pitch, with the pitch clamped so we never flip over the pole
+    fn turntable_from_angles(&self, yaw: f32, pitch: f32) -> Quaternion<f32> {
+        let pitch = pitch.clamp(-MAX_TURNTABLE_PITCH, MAX_TURNTABLE_PITCH);
+        let up_frame = Quaternion::from_arc(Vector3::unit_y(), self.up.normalize(), None);
+        up_frame * Quaternion::from_angle_y(Rad(yaw)) * Quaternion::from_angle_x(Rad(-pitch))
+    }
+
     /// Handle mouse movement as pixel coordinates
     pub fn handle_mouse_move(&mut self, mouse_x: f32, mouse_y: f32) {
+        let last_mouse_coords = self.prev_mouse_coords;
         self.prev_mouse_coords = Vector2::new(mouse_x, mouse_y);
 
         match self.state {
@@ -356,61 +929,177 @@ impl Camera {
                 // The original and new sphere point define a rotation
                 // so we convert that into a quaternion and update the camera's rotation
                 let sphere_point = self.mouse_to_sphere_point(self.prev_mouse_coords);
-                let rotation_axis = self.original_sphere_point.cross(sphere_point);
-                let scalar = self.original_sphere_point.dot(sphere_point);
-                let move_rotation = Quaternion::from_sv(scalar, rotation_axis);
+                let move_rotation =
+                    self.trackball_rotation(self.original_sphere_point, sphere_point);
                 let new_rotation = self.original_rotation * move_rotation;
                 self.rotation = new_rotation;
+                self.record_tumble_sample();
+            }
+            CamState::Turntable => {
+                self.rotation = self.turntable_rotation(self.prev_mouse_coords);
+                self.record_tumble_sample();
             }
             CamState::Pan => {
                 // The original and new pan point define a translation
                 let pan_point = self.mouse_to_pan_point(self.prev_mouse_coords);
                 let pan_delta = self.original_pan_point - pan_point;
                 self.target = self.original_target + pan_delta;
+                self.record_pan_sample();
+            }
+            CamState::Zoom => {
+                // Moving the mouse down zooms out like scrolling down the same number of pixels
+                let pixel_delta = self.prev_mouse_coords.y - last_mouse_coords.y;
+                self.zoom_pivot = None;
+                self.add_zoom_goal(zoom_scale(pixel_delta * self.scroll_modifier));
+            }
+            CamState::Roll => {
+                // The scene turns with the mouse as it circles the middle of the window
+                let last = self.mouse_to_screen(last_mouse_coords);
+                let current = self.mouse_to_screen(self.prev_mouse_coords);
+                self.roll_screen(last, current);
             }
             _ => (),
         }
     }
 
-    pub fn toggle_orbit(&mut self) {
-        self.orbit_enabled = !self.orbit_enabled;
+    /// Handle the modifier keys changing, the bindings look at them when a button or key is
+    /// pressed and shift boosts flying
+    pub fn handle_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+        self.set_fly_boost(modifiers.shift);
     }
 
-    /// Handle mouse clicks,
+    /// Handle mouse clicks, what a press does depends on the bindings and the modifiers that are
+    /// held
     pub fn handle_mouse_input(&mut self, button: MouseButton, state: ButtonState) {
-        match (button, state) {
-            (MouseButton::Left, ButtonState::Pressed) => {
-                self.state = CamState::Tumble;
+        // While flying the mouse is only used for looking around
+        if self.state == CamState::Fly {
+            return;
+        }
+
+        match state {
+            ButtonState::Pressed => {
+                if let Some(action) = self.bindings.drag_action(button, self.modifiers) {
+                    self.start_drag(action);
+                    self.drag_button = Some(button);
+                }
+            }
+            ButtonState::Released => {
+                if self.drag_button == Some(button) {
+                    self.end_drag();
+                }
+            }
+        }
+    }
+
+    // Turn the scene on screen by the angle between the two screen space directions, which means
+    // rolling the camera the other way around its view direction
+    fn roll_screen(&mut self, from: Vector2<f32>, to: Vector2<f32>) {
+        let angle = from.perp_dot(to).atan2(from.dot(to));
+        self.rotation = self.rotation * Quaternion::from_angle_z(Rad(-angle));
+    }
+
+    fn end_drag(&mut self) {
+        self.drag_button = None;
+        let dragging = matches!(
+            self.state,
+            CamState::Tumble
+                | CamState::Turntable
+                | CamState::Pan
+                | CamState::Zoom
+                | CamState::Roll
+                | CamState::Touch
+        );
+        if !dragging {
+            return;
+        }
+
+        if self.state == CamState::Pan && self.pan_time_constant > 0.0 {
+            self.start_pan_momentum();
+        }
+
+        let tumbling = self.state == CamState::Tumble || self.state == CamState::Turntable;
+        if self.orbit_enabled && tumbling && self.start_orbit() {
+            self.state = CamState::IdleOrbit;
+        } else {
+            self.state = CamState::Idle;
+        }
+    }
+
+    fn start_drag(&mut self, action: DragAction) {
+        self.drag_button = None;
+        match action {
+            DragAction::Tumble => {
+                self.state = match self.orbit_style {
+                    OrbitStyle::Arcball => CamState::Tumble,
+                    OrbitStyle::Turntable => CamState::Turntable,
+                };
+                self.pan_velocity = Vector3::zero();
                 self.tumble_duration = 0.0;
-                self.original_sphere_point = self.mouse_to_sphere_point(self.prev_mouse_coords);
+                self.original_mouse_coords = self.prev_mouse_coords;
                 self.original_rotation = self.rotation.clone();
+                self.original_sphere_point = self.mouse_to_sphere_point(self.prev_mouse_coords);
+                self.tumble_samples.clear();
+                self.record_tumble_sample();
             }
-            (MouseButton::Right, ButtonState::Pressed) => {
+            DragAction::Pan => {
                 self.state = CamState::Pan;
                 self.original_pan_point = self.mouse_to_pan_point(self.prev_mouse_coords);
                 self.original_target = self.target.clone();
+                self.pan_duration = 0.0;
+                self.pan_velocity = Vector3::zero();
+                self.pan_samples.clear();
+                self.record_pan_sample();
             }
-            (_, ButtonState::Released) => {
-                if self.orbit_enabled && self.state == CamState::Tumble {
-                    self.state = CamState::IdleOrbit;
-
-                //let angle_delta= self.last_tumble_delta.s.cos() * 2.0;
-                //let angle_delta = new_angle - old_angle;
-                //self.orbit_velocity = (angle_delta * 1000.0) / self.tumble_duration;
-                } else {
-                    self.state = CamState::Idle;
-                }
+            DragAction::Zoom => {
+                self.state = CamState::Zoom;
+            }
+            DragAction::Roll => {
+                self.state = CamState::Roll;
             }
         }
     }
 
     // Handle scroll events as pixel deltas
     pub fn handle_scroll(&mut self, pixel_delta: f32) {
-        let normalized_delta = pixel_delta * self.scroll_modifier;
+        self.handle_scroll_at_depth(pixel_delta, None);
+    }
 
-        let scale = 1.0 + normalized_delta;
+    /// Handle a scroll event when the application knows the depth under the mouse, for example
+    /// from reading back the depth buffer. The depth is in normalized device coordinates, like
+    /// the depth of a `ScreenPoint`. With zoom to cursor on, the point at that depth stays put,
+    /// and without a depth the point under the mouse at the target's depth is used.
+    pub fn handle_scroll_at_depth(&mut self, pixel_delta: f32, depth: Option<f32>) {
+        self.scroll_by(pixel_delta * self.scroll_modifier, depth);
+    }
 
-        self.distance *= scale;
+    /// Handle scroll events from wheels that report lines instead of pixels, which is what most
+    /// notched mouse wheels do
+    pub fn handle_line_scroll(&mut self, lines: f32) {
+        self.handle_line_scroll_at_depth(lines, None);
+    }
+
+    /// Handle a line scroll when the application knows the depth under the mouse, see
+    /// `handle_scroll_at_depth`
+    pub fn handle_line_scroll_at_depth(&mut self, lines: f32, depth: Option<f32>) {
+        self.scroll_by(lines * self.line_scroll_modifier, depth);
+    }
+
+    fn scroll_by(&mut self, normalized_delta: f32, depth: Option<f32>) {
+        let scale = zoom_scale(normalized_delta);
+
+        // There is nothing to zoom while flying, so scrolling adjusts the speed instead
+        if self.state == CamState::Fly {
+            self.fly_speed /= scale;
+            return;
+        }
+
+        self.zoom_pivot = if self.zoom_to_cursor {
+            Some(self.zoom_pivot(depth))
+        } else {
+            None
+        };
+        self.add_zoom_goal(scale);
     }
 
     /// Move the camera's target
@@ -419,11 +1108,39 @@ impl Camera {
     }
 }
 
+// Pixel scrolls, line scrolls and zoom drags all turn their scaled delta into a zoom factor here.
+// The factor is exponential, so however big the delta, as from an accelerated wheel or a fast
+// flick, it never reaches zero and flips the camera through the target.
+fn zoom_scale(normalized_delta: f32) -> f32 {
+    normalized_delta.exp()
+}
+
+// This is Shoemake's constraint, the sphere point is projected onto the great circle that is
+// perpendicular to the axis, so any two constrained points define a rotation around the axis
+fn constrain_to_axis(sphere_point: Vector3<f32>, axis: Vector3<f32>) -> Vector3<f32> {
+    let on_plane = sphere_point - axis * axis.dot(sphere_point);
+    let length = on_plane.magnitude();
+    if length > f32::EPSILON {
+        // Stay on the half of the circle facing the viewer, which has negative z
+        if on_plane.z > 0.0 {
+            -on_plane / length
+        } else {
+            on_plane / length
+        }
+    } else if axis.z.abs() >= 1.0 - f32::EPSILON {
+        Vector3::unit_x()
+    } else {
+        Vector3::new(-axis.y, axis.x, 0.0).normalize()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use assert::*;
+    use bounds::{Aabb, Sphere};
     use cgmath::vec2;
+    use input::{CameraInput, Key, KeyAction};
     use std::default::Default;
     use std::f32;
 
@@ -511,4 +1228,918 @@ mod tests {
         close(&screen_point, &[0.0, 0.0], f32::EPSILON);
     }
 
+    #[test]
+    fn test_orthographic_pan_matches_projection() {
+        let mut camera = make_cam_with_window(1024.0, 512.0);
+        camera.set_projection_mode(ProjectionMode::Orthographic);
+
+        // Dragging from the center to the right edge should move the world by exactly the
+        // distance that the projection maps from the center to the right edge
+        let center = camera.mouse_to_pan_point(vec2(512.0, 256.0));
+        let edge = camera.mouse_to_pan_point(vec2(1024.0, 256.0));
+        let pan_distance = (edge - center).magnitude();
+
+        let clip = camera.get_clipspace_transform() * (camera.target + (edge - center)).extend(1.0);
+        close(clip.x / clip.w, 1.0, 1e-4);
+        close(
+            pan_distance,
+            camera.ortho_extent * camera.aspect_ratio,
+            1e-3,
+        );
+    }
+
+    #[test]
+    fn test_orthographic_scroll_changes_extent() {
+        let mut camera = make_cam_with_window(512.0, 512.0);
+        camera.set_projection_mode(ProjectionMode::Orthographic);
+        let distance = camera.distance;
+        let extent = camera.ortho_extent;
+
+        camera.handle_scroll(100.0);
+        close(camera.distance, distance, f32::EPSILON);
+        close(camera.ortho_extent, extent * 0.5f32.exp(), 1e-4);
+    }
+
+    #[test]
+    fn test_pixel_to_ray() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.set_clip_space(ClipSpace::vulkan());
+        camera.set_depth_mode(DepthMode::InfiniteReversed);
+
+        // The center of the window looks straight at the target
+        let ray = camera.pixel_to_ray(400.0, 300.0);
+        let to_target = (camera.target - camera.get_position()).normalize();
+        let direction: [f32; 3] = ray.direction.into();
+        let to_target: [f32; 3] = to_target.into();
+        close(&direction, &to_target, 1e-4);
+
+        // The top left corner of the window runs along the edge of the frustum, which is up and
+        // to the left in eye space
+        let ray = camera.pixel_to_ray(0.0, 0.0);
+        let eye_direction = camera.get_rotation().invert().rotate_vector(ray.direction);
+        let half_height = (camera.field_of_view / 2.0).tan();
+        let expected = Vector3::new(-half_height * 800.0 / 600.0, half_height, -1.0).normalize();
+        let eye_direction: [f32; 3] = eye_direction.into();
+        let expected: [f32; 3] = expected.into();
+        close(&eye_direction, &expected, 1e-4);
+    }
+
+    #[test]
+    fn test_world_to_pixel_round_trip() {
+        for &clip_space in &[ClipSpace::opengl(), ClipSpace::vulkan()] {
+            let mut camera = make_cam_with_window(800.0, 600.0);
+            camera.set_clip_space(clip_space);
+
+            let ray = camera.pixel_to_ray(100.0, 500.0);
+            let screen_point = camera.world_to_pixel(ray.at(30.0));
+            let pixel: [f32; 2] = screen_point.pixel.into();
+            // Unprojecting loses some precision with the default near to far ratio
+            close(&pixel, &[100.0, 500.0], 0.25);
+            assert!(screen_point.in_front);
+
+            // Mirroring the point through the camera puts it behind
+            let behind = 2.0 * camera.get_position() - ray.at(30.0);
+            assert!(!camera.world_to_pixel(behind).in_front);
+        }
+    }
+
+    #[test]
+    fn test_fly_mode_keeps_view() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.enter_fly_mode();
+
+        // Looking around turns the camera in place
+        let position: [f32; 3] = camera.get_position().into();
+        camera.handle_mouse_motion(120.0, -40.0);
+        let new_position: [f32; 3] = camera.get_position().into();
+        close(&new_position, &position, 1e-3);
+
+        // Flying forward for a second moves fly speed units along the view direction
+        let forward = camera.rotation.rotate_vector(-Vector3::unit_z());
+        let expected: [f32; 3] = (camera.get_position() + forward * camera.fly_speed).into();
+        camera.handle_fly_key(FlyDirection::Forward, ButtonState::Pressed);
+        camera.update(1000.0, 800.0, 600.0);
+        camera.handle_fly_key(FlyDirection::Forward, ButtonState::Released);
+        let position: [f32; 3] = camera.get_position().into();
+        close(&position, &expected, 1e-3);
+
+        // Leaving fly mode does not move the camera
+        let rotation = camera.rotation;
+        camera.exit_fly_mode();
+        let new_position: [f32; 3] = camera.get_position().into();
+        close(&new_position, &position, 1e-3);
+        assert_eq!(camera.rotation, rotation);
+    }
+
+    #[test]
+    fn test_turntable_keeps_horizon_level() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.set_orbit_style(OrbitStyle::Turntable);
+
+        camera.handle_mouse_move(400.0, 300.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        camera.handle_mouse_move(700.0, 250.0);
+        let right = camera.rotation.rotate_vector(Vector3::unit_x());
+        close(right.y, 0.0, 1e-5);
+
+        // Dragging way up would flip over the pole, but the pitch is clamped
+        camera.handle_mouse_move(700.0, -5000.0);
+        let back = camera.rotation.rotate_vector(Vector3::unit_z());
+        assert!(back.y.asin() >= -MAX_TURNTABLE_PITCH - 1e-4);
+        let right = camera.rotation.rotate_vector(Vector3::unit_x());
+        close(right.y, 0.0, 1e-5);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+    }
+
+    #[test]
+    fn test_turntable_orbit_keeps_horizon_level() {
+        // A vertical flick each way, and a mixed one. The flick is short, so it is the orbit that
+        // carries the pitch on into the clamp
+        for &(dx, dy) in &[(0.0, -10.0), (0.0, 10.0), (10.0, -10.0)] {
+            let mut camera = make_cam_with_window(800.0, 600.0);
+            camera.set_orbit_style(OrbitStyle::Turntable);
+            camera.toggle_orbit();
+
+            camera.handle_mouse_move(400.0, 300.0);
+            camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+            for i in 1..5 {
+                camera.update(10.0, 800.0, 600.0);
+                camera.handle_mouse_move(400.0 + dx * i as f32, 300.0 + dy * i as f32);
+            }
+            camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+            assert!(camera.state == CamState::IdleOrbit);
+
+            for _ in 0..200 {
+                camera.update(10.0, 800.0, 600.0);
+                let back = camera.rotation.rotate_vector(Vector3::unit_z());
+                assert!(back.y.abs().asin() <= MAX_TURNTABLE_PITCH + 1e-4);
+                assert!(camera.rotation.rotate_vector(Vector3::unit_y()).y >= 0.0);
+                close(
+                    camera.rotation.rotate_vector(Vector3::unit_x()).y,
+                    0.0,
+                    1e-4,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_trackball_rotation_amount() {
+        let mut camera = make_cam_with_window(512.0, 512.0);
+
+        // Dragging from the middle of the ball to its edge is a quarter turn
+        camera.handle_mouse_move(256.0, 256.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        let start_rotation = camera.rotation;
+        camera.handle_mouse_move(512.0, 256.0);
+        let delta = start_rotation.invert() * camera.rotation;
+        close(2.0 * delta.s.acos(), f32::consts::PI / 2.0, 1e-4);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+
+        // Out on the hyperbolic sheet Bell's trackball turns more than Holroyd's
+        let mut angles = Vec::new();
+        for &mapping in &[TrackballMapping::Bell, TrackballMapping::Holroyd] {
+            camera.set_trackball_mapping(mapping);
+            camera.handle_mouse_move(256.0, 256.0);
+            camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+            let start_rotation = camera.rotation;
+            camera.handle_mouse_move(1024.0, 256.0);
+            let delta = start_rotation.invert() * camera.rotation;
+            angles.push(2.0 * delta.s.acos());
+            camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+        }
+        assert!(angles[0] > angles[1]);
+        assert!(angles[1] < f32::consts::PI / 2.0);
+    }
+
+    #[test]
+    fn test_orbit_inertia() {
+        let mut camera = make_cam_with_window(512.0, 512.0);
+        camera.toggle_orbit();
+
+        // Spin steadily around the camera's y-axis, a twentieth of a radian every 10 milliseconds
+        camera.set_axis_constraint(Some(AxisConstraint::CameraY));
+        camera.handle_mouse_move(256.0, 256.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        for i in 1..20 {
+            camera.update(10.0, 512.0, 512.0);
+            let x = 256.0 + 256.0 * (0.05 * i as f32).sin();
+            camera.handle_mouse_move(x, 256.0);
+        }
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+        assert!(camera.state == CamState::IdleOrbit);
+        close(camera.orbit_velocity, 5.0, 1e-2);
+        let axis: [f32; 3] = camera.orbit_axis.into();
+        close(&axis, &[0.0, -1.0, 0.0], 1e-4);
+
+        // The orbit carries on turning, slows down, and eventually stops
+        let rotation = camera.rotation;
+        camera.update(10.0, 512.0, 512.0);
+        let delta = rotation.invert() * camera.rotation;
+        close(2.0 * delta.s.acos(), 0.05, 1e-3);
+        close(camera.rotation.magnitude(), 1.0, 1e-5);
+        for _ in 0..1000 {
+            camera.update(10.0, 512.0, 512.0);
+        }
+        assert!(camera.state == CamState::Idle);
+
+        // Holding still before letting go means there is nothing to carry on with
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        camera.handle_mouse_move(300.0, 256.0);
+        camera.update(500.0, 512.0, 512.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+        assert!(camera.state == CamState::Idle);
+    }
+
+    #[test]
+    fn test_smooth_zoom_and_pan_momentum() {
+        let mut camera = make_cam_with_window(512.0, 512.0);
+        camera.set_zoom_time_constant(100.0);
+        camera.set_pan_time_constant(200.0);
+
+        // Scrolling does nothing until update, then eases in to the same place as before
+        let distance = camera.distance;
+        camera.handle_scroll(100.0);
+        camera.handle_scroll(-50.0);
+        close(camera.distance, distance, f32::EPSILON);
+        camera.update(16.0, 512.0, 512.0);
+        assert!(camera.distance > distance && camera.distance < distance * 0.25f32.exp());
+        for _ in 0..100 {
+            camera.update(16.0, 512.0, 512.0);
+        }
+        close(camera.distance, distance * 0.25f32.exp(), 1e-3);
+
+        // A pan that is let go while moving keeps going for a while, then stops
+        camera.handle_mouse_move(256.0, 256.0);
+        camera.handle_mouse_input(MouseButton::Right, ButtonState::Pressed);
+        for i in 1..10 {
+            camera.update(10.0, 512.0, 512.0);
+            camera.handle_mouse_move(256.0 - 5.0 * i as f32, 256.0);
+        }
+        camera.handle_mouse_input(MouseButton::Right, ButtonState::Released);
+        let target = camera.target;
+        camera.update(10.0, 512.0, 512.0);
+        let velocity = camera.target - target;
+        assert!(velocity.magnitude() > 0.0);
+        for _ in 0..1000 {
+            camera.update(10.0, 512.0, 512.0);
+        }
+        let target = camera.target;
+        camera.update(10.0, 512.0, 512.0);
+        assert_eq!(camera.target, target);
+
+        // Starting a tumble stops the momentum from an earlier pan for good
+        camera.handle_mouse_input(MouseButton::Right, ButtonState::Pressed);
+        for i in 1..10 {
+            camera.update(10.0, 512.0, 512.0);
+            camera.handle_mouse_move(256.0 + 5.0 * i as f32, 256.0);
+        }
+        camera.handle_mouse_input(MouseButton::Right, ButtonState::Released);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+        let target = camera.target;
+        camera.update(10.0, 512.0, 512.0);
+        assert_eq!(camera.target, target);
+    }
+
+    #[test]
+    fn test_camera_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Camera>();
+    }
+
+    #[test]
+    fn test_eased_transition() {
+        let mut camera = make_cam_with_window(512.0, 512.0);
+        let start = camera.target;
+        let end = Vector3::new(10.0, 0.0, 0.0);
+        camera.start_transition_with_easing(end, camera.rotation, 50.0, 100.0, Easing::CubicIn);
+
+        // A quarter of the way through the time, cubic in has only gone 1/64th of the way
+        camera.update(25.0, 512.0, 512.0);
+        let target: [f32; 3] = camera.target.into();
+        let expected: [f32; 3] = (start + (end - start) / 64.0).into();
+        close(&target, &expected, 1e-5);
+
+        camera.update(100.0, 512.0, 512.0);
+        assert!(camera.state == CamState::Idle);
+        assert_eq!(camera.target, end);
+    }
+
+    #[test]
+    fn test_bookmarks_and_view_stack() {
+        let mut camera = make_cam_with_window(512.0, 512.0);
+        let home = camera.current_view();
+
+        camera.set_target(Vector3::new(1.0, 2.0, 3.0));
+        camera.set_field_of_view(1.0);
+        camera.save_bookmark(1);
+        camera.set_target(Vector3::new(-5.0, 0.0, 0.0));
+        camera.save_bookmark("side");
+        camera.set_view(&home);
+
+        let keys: Vec<_> = camera.bookmarks().map(|(key, _)| key.clone()).collect();
+        assert_eq!(
+            keys,
+            vec![BookmarkKey::Number(1), BookmarkKey::from("side")]
+        );
+
+        // Recalling animates there, field of view included
+        assert!(camera.recall_bookmark(1));
+        camera.update(1000.0, 512.0, 512.0);
+        assert_eq!(camera.current_view(), *camera.bookmark(1).unwrap());
+
+        // Going back returns to where we were before the recall
+        camera.transition_back();
+        camera.update(1000.0, 512.0, 512.0);
+        assert_eq!(camera.current_view(), home);
+
+        assert!(camera.delete_bookmark("side").is_some());
+        assert!(!camera.recall_bookmark("side"));
+    }
+
+    #[test]
+    fn test_standard_views() {
+        let mut camera = make_cam_with_window(512.0, 512.0);
+        let target = camera.target;
+
+        // Top looks straight down from above the target
+        camera.transition_to_standard_view(StandardView::Top, Easing::SmoothStep);
+        camera.update(1000.0, 512.0, 512.0);
+        let offset: [f32; 3] = (camera.get_position() - target).normalize().into();
+        close(&offset, &[0.0, 1.0, 0.0], 1e-5);
+
+        // Isometric looks along the diagonal
+        camera.transition_to_standard_view(StandardView::Isometric, Easing::Linear);
+        camera.update(1000.0, 512.0, 512.0);
+        let offset: [f32; 3] = (camera.get_position() - target).normalize().into();
+        let diagonal = 1.0 / 3.0f32.sqrt();
+        close(&offset, &[diagonal, diagonal, diagonal], 1e-5);
+
+        // A slightly tumbled right view snaps back to the right view
+        let right = StandardView::Right.rotation();
+        camera.set_rotation(right * Quaternion::from_angle_x(Rad(0.2)));
+        camera.snap_to_nearest_view(Easing::Linear);
+        camera.update(1000.0, 512.0, 512.0);
+        close(camera.rotation.dot(right).abs(), 1.0, 1e-5);
+    }
+
+    #[test]
+    fn test_zoom_to_cursor() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.set_zoom_to_cursor(true);
+        camera.set_distance(10.0);
+        camera.handle_mouse_move(600.0, 150.0);
+
+        // Without a depth, the point on the target's plane under the mouse stays put. The pixel
+        // tolerance is loose for the same precision reason as the world_to_pixel round trip.
+        let pivot = camera.zoom_pivot(None);
+        camera.handle_scroll(-100.0);
+        close(camera.distance, 10.0 * (-0.5f32).exp(), 1e-5);
+        let pixel: [f32; 2] = camera.world_to_pixel(pivot).pixel.into();
+        close(&pixel, &[600.0, 150.0], 0.25);
+
+        // With a depth, that point stays put even when it is off the target's plane
+        let point = camera.pixel_to_ray(600.0, 150.0).at(2.0);
+        let depth = camera.world_to_pixel(point).depth;
+        let point_distance = (camera.get_position() - point).magnitude();
+        camera.handle_scroll_at_depth(-100.0, Some(depth));
+        let pixel: [f32; 2] = camera.world_to_pixel(point).pixel.into();
+        close(&pixel, &[600.0, 150.0], 0.25);
+        close(
+            (camera.get_position() - point).magnitude(),
+            point_distance * (-0.5f32).exp(),
+            1e-3,
+        );
+
+        // Orthographic zooming keeps the point under the mouse too
+        camera.set_projection_mode(ProjectionMode::Orthographic);
+        let pivot = camera.zoom_pivot(None);
+        camera.handle_scroll(100.0);
+        let pixel: [f32; 2] = camera.world_to_pixel(pivot).pixel.into();
+        close(&pixel, &[600.0, 150.0], 0.25);
+    }
+
+    #[test]
+    fn test_large_scroll_keeps_distance_positive() {
+        // A fast trackpad flick can report more pixels than would take a linear scale to zero
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.set_distance(10.0);
+        camera.handle_scroll(-250.0);
+        close(camera.distance, 10.0 * (-1.25f32).exp(), 1e-5);
+
+        camera.set_distance(10.0);
+        camera.set_zoom_time_constant(100.0);
+        camera.handle_scroll(-250.0);
+        for _ in 0..100 {
+            camera.update(16.0, 800.0, 600.0);
+            assert!(camera.distance.is_finite() && camera.distance > 0.0);
+        }
+        close(camera.distance, 10.0 * (-1.25f32).exp(), 1e-3);
+    }
+
+    #[test]
+    fn test_zoom_modes() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.set_distance(10.0);
+        let half_tan = (camera.field_of_view / 2.0).tan();
+
+        camera.set_zoom_mode(ZoomMode::FieldOfView);
+        camera.handle_scroll(-100.0);
+        close(camera.distance, 10.0, 1e-5);
+        close(
+            (camera.field_of_view / 2.0).tan(),
+            half_tan * (-0.5f32).exp(),
+            1e-5,
+        );
+        for _ in 0..20 {
+            camera.handle_scroll(-100.0);
+        }
+        close(camera.field_of_view, camera.min_field_of_view, 1e-5);
+
+        // The target stays the same size on screen, even once the field of view hits a limit
+        camera.set_field_of_view(f32::consts::PI / 2.0);
+        camera.set_zoom_mode(ZoomMode::DollyZoom);
+        camera.handle_scroll(-100.0);
+        close(camera.distance, 10.0 * (-0.5f32).exp(), 1e-4);
+        close(
+            camera.distance * (camera.field_of_view / 2.0).tan(),
+            10.0,
+            1e-4,
+        );
+        for _ in 0..20 {
+            camera.handle_scroll(-100.0);
+        }
+        close(camera.field_of_view, camera.max_field_of_view, 1e-5);
+        close(
+            camera.distance * (camera.field_of_view / 2.0).tan(),
+            10.0,
+            1e-4,
+        );
+
+        // Part way through a transition the size holds as well
+        camera.start_zoom_transition(1000.0, 1000.0, Easing::Linear);
+        camera.update(500.0, 800.0, 600.0);
+        close(
+            camera.distance * (camera.field_of_view / 2.0).tan(),
+            10.0,
+            1e-4,
+        );
+        camera.update(500.0, 800.0, 600.0);
+        close(camera.field_of_view, camera.min_field_of_view, 1e-5);
+        close(
+            camera.distance * (camera.field_of_view / 2.0).tan(),
+            10.0,
+            1e-3,
+        );
+    }
+
+    #[test]
+    fn test_save_and_restore_state() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.set_target(Vector3::new(1.0, 2.0, 3.0));
+        camera.set_rotation(StandardView::Isometric.rotation());
+        camera.set_distance(7.0);
+        camera.set_scroll_modifier(0.01);
+        camera.set_current_as_default();
+        camera.set_near(0.5);
+        camera.set_projection_mode(ProjectionMode::Orthographic);
+        camera.set_depth_mode(DepthMode::Reversed);
+        camera.set_clip_space(ClipSpace::vulkan());
+        camera.set_up(Vector3::unit_z());
+        let saved = camera.save_state();
+
+        // Restoring stops whatever the camera was in the middle of
+        let mut other = make_cam_with_window(800.0, 600.0);
+        other.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        other.restore_state(&saved);
+        assert!(other.state == CamState::Idle);
+        assert_eq!(other.save_state(), saved);
+        assert!(other.projection_mode == ProjectionMode::Orthographic);
+        close(other.ortho_extent, camera.ortho_extent, f32::EPSILON);
+        assert!(other.depth_mode == DepthMode::Reversed);
+        assert_eq!(other.up, Vector3::unit_z());
+
+        // A state from a newer version still restores everything this version knows about
+        let mut newer = saved.clone();
+        newer.version = CAMERA_STATE_VERSION + 1;
+        let mut another = make_cam_with_window(800.0, 600.0);
+        another.restore_state(&newer);
+        let restored = CameraState {
+            version: newer.version,
+            ..another.save_state()
+        };
+        assert_eq!(restored, newer);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_state_serde_round_trip() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.set_distance(7.0);
+        let saved = camera.save_state();
+        let json = serde_json::to_string(&saved).unwrap();
+        assert_eq!(serde_json::from_str::<CameraState>(&json).unwrap(), saved);
+
+        // Fields missing from an older file fall back to their defaults
+        let partial: CameraState =
+            serde_json::from_str(r#"{"version": 1, "distance": 3.0}"#).unwrap();
+        close(partial.distance, 3.0, f32::EPSILON);
+        close(partial.far, CameraState::default().far, f32::EPSILON);
+
+        // Fields from a newer file that this version does not know about are skipped
+        let newer: CameraState =
+            serde_json::from_str(r#"{"version": 2, "distance": 3.0, "exposure": 1.5}"#).unwrap();
+        close(newer.distance, 3.0, f32::EPSILON);
+    }
+
+    #[test]
+    fn test_path_playback() {
+        let mut path = CameraPath::new();
+        let mut view = Camera::new().current_view();
+        path.add_keyframe(0.0, view);
+        view.distance = 20.0;
+        path.add_keyframe(1000.0, view);
+
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.play_path(path.clone(), PlaybackMode::Once);
+        camera.update(500.0, 800.0, 600.0);
+        close(camera.path_time(), 500.0, f32::EPSILON);
+        close(camera.distance, path.sample(500.0).unwrap().distance, 1e-5);
+        camera.update(700.0, 800.0, 600.0);
+        assert!(!camera.is_playing_path());
+        close(camera.distance, 20.0, 1e-5);
+
+        camera.play_path(path.clone(), PlaybackMode::Loop);
+        camera.update(1200.0, 800.0, 600.0);
+        close(camera.path_time(), 200.0, 1e-3);
+
+        // Ping pong turns around at the end, and keeps going the other way after that
+        camera.set_path_speed(2.0);
+        camera.play_path(path.clone(), PlaybackMode::PingPong);
+        camera.update(600.0, 800.0, 600.0);
+        close(camera.path_time(), 800.0, 1e-3);
+        camera.update(200.0, 800.0, 600.0);
+        close(camera.path_time(), 400.0, 1e-3);
+
+        // Scrubbing works while paused
+        camera.set_path_paused(true);
+        camera.scrub_path(250.0);
+        camera.update(100.0, 800.0, 600.0);
+        close(camera.path_time(), 250.0, f32::EPSILON);
+        close(camera.distance, path.sample(250.0).unwrap().distance, 1e-5);
+
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        assert!(!camera.is_playing_path());
+    }
+
+    #[test]
+    fn test_camera_input() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        let inputs = [
+            CameraInput::CursorMoved { x: 400.0, y: 300.0 },
+            CameraInput::MouseButton {
+                button: MouseButton::Left,
+                state: ButtonState::Pressed,
+            },
+            CameraInput::CursorMoved { x: 500.0, y: 300.0 },
+            CameraInput::MouseButton {
+                button: MouseButton::Left,
+                state: ButtonState::Released,
+            },
+        ];
+        for &input in &inputs {
+            camera.handle_input(input);
+        }
+        assert!(camera.rotation != Quaternion::one());
+
+        // Control and a number saves a bookmark
+        camera.handle_input(CameraInput::Modifiers(Modifiers {
+            ctrl: true,
+            ..Modifiers::default()
+        }));
+        camera.handle_input(CameraInput::Key {
+            key: Key::Digit(3),
+            state: ButtonState::Pressed,
+        });
+        assert_eq!(camera.bookmark(3).unwrap().rotation, camera.rotation);
+
+        camera.handle_input(CameraInput::Character('f'));
+        assert!(camera.is_flying());
+    }
+
+    #[test]
+    fn test_bindings() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.set_bindings(Bindings::maya());
+        camera.set_distance(10.0);
+        let alt = Modifiers {
+            alt: true,
+            ..Modifiers::default()
+        };
+
+        // Without alt the left button is left for the application
+        camera.handle_mouse_move(400.0, 300.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        assert!(camera.state == CamState::Idle);
+
+        camera.handle_input(CameraInput::Modifiers(alt));
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        assert!(camera.state == CamState::Tumble);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+
+        // Dragging down zooms out like scrolling does
+        camera.handle_mouse_input(MouseButton::Right, ButtonState::Pressed);
+        camera.handle_mouse_move(400.0, 400.0);
+        camera.handle_mouse_input(MouseButton::Right, ButtonState::Released);
+        close(camera.distance, 10.0 * 0.5f32.exp(), 1e-4);
+
+        // Rolling a quarter turn around the middle of the window keeps the view direction
+        camera
+            .bindings_mut()
+            .bind_drag(MouseButton::Right, alt, DragAction::Roll);
+        let forward: [f32; 3] = camera.rotation.rotate_vector(-Vector3::unit_z()).into();
+        camera.handle_mouse_move(500.0, 300.0);
+        camera.handle_mouse_input(MouseButton::Right, ButtonState::Pressed);
+        camera.handle_mouse_move(400.0, 200.0);
+        let rolled: [f32; 3] = camera.rotation.rotate_vector(-Vector3::unit_z()).into();
+        close(&rolled, &forward, 1e-5);
+        let right: [f32; 3] = camera.rotation.rotate_vector(Vector3::unit_x()).into();
+        close(&right, &[0.0, -1.0, 0.0], 1e-5);
+
+        // The key without modifiers is used when nothing is bound to the ones held, as long as the
+        // key has no bindings with modifiers at all
+        let bindings = Bindings::aperture();
+        let shift = Modifiers {
+            shift: true,
+            ..Modifiers::default()
+        };
+        assert_eq!(
+            bindings.key_action(Key::Letter('w'), shift),
+            Some(KeyAction::Fly(FlyDirection::Forward))
+        );
+        assert_eq!(
+            bindings.key_action(Key::Letter('x'), shift),
+            Some(KeyAction::ConstrainAxis(AxisConstraint::CameraX))
+        );
+        assert_eq!(bindings.key_action(Key::Digit(3), shift), None);
+        assert_eq!(
+            bindings.key_action(Key::Digit(3), Modifiers::default()),
+            Some(KeyAction::RecallBookmark(3))
+        );
+    }
+
+    #[test]
+    fn test_modifier_drags() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.set_distance(10.0);
+        camera.handle_mouse_move(400.0, 300.0);
+
+        // Shift and the left button pans for anyone without a right button
+        camera.handle_modifiers(Modifiers {
+            shift: true,
+            ..Modifiers::default()
+        });
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        assert!(camera.state == CamState::Pan);
+        camera.handle_mouse_move(300.0, 300.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+
+        // It pans exactly as far as the same drag with the right button
+        let mut right = make_cam_with_window(800.0, 600.0);
+        right.set_distance(10.0);
+        right.handle_mouse_move(400.0, 300.0);
+        right.handle_mouse_input(MouseButton::Right, ButtonState::Pressed);
+        right.handle_mouse_move(300.0, 300.0);
+        right.handle_mouse_input(MouseButton::Right, ButtonState::Released);
+        assert!(right.target != Vector3::zero());
+        let target: [f32; 3] = camera.target.into();
+        let expected: [f32; 3] = right.target.into();
+        close(&target, &expected, 1e-5);
+
+        camera.handle_modifiers(Modifiers {
+            ctrl: true,
+            ..Modifiers::default()
+        });
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        assert!(camera.state == CamState::Zoom);
+        camera.handle_mouse_move(300.0, 200.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+        close(camera.distance, 10.0 * (-0.5f32).exp(), 1e-4);
+
+        // That is the same zoom as scrolling the same number of pixels
+        let mut scrolled = make_cam_with_window(800.0, 600.0);
+        scrolled.set_distance(10.0);
+        scrolled.handle_scroll(-100.0);
+        close(camera.distance, scrolled.distance, 1e-4);
+
+        // A fast flick far past the point where a linear scale would reach zero still zooms in,
+        // with or without smoothing
+        for &time_constant in &[0.0, 100.0] {
+            let mut camera = make_cam_with_window(800.0, 600.0);
+            camera.set_distance(10.0);
+            camera.set_zoom_time_constant(time_constant);
+            camera.handle_modifiers(Modifiers {
+                ctrl: true,
+                ..Modifiers::default()
+            });
+            camera.handle_mouse_move(400.0, 500.0);
+            camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+            camera.handle_mouse_move(400.0, 100.0);
+            camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+            for _ in 0..100 {
+                camera.update(16.0, 800.0, 600.0);
+            }
+            close(camera.distance, 10.0 * (-2.0f32).exp(), 1e-3);
+        }
+
+        // Extra buttons do nothing until they are bound
+        camera.handle_modifiers(Modifiers::default());
+        camera.handle_mouse_input(MouseButton::Back, ButtonState::Pressed);
+        assert!(camera.state == CamState::Idle);
+        camera.bindings_mut().bind_drag(
+            MouseButton::Other(8),
+            Modifiers::default(),
+            DragAction::Roll,
+        );
+        camera.handle_mouse_input(MouseButton::Other(8), ButtonState::Pressed);
+        assert!(camera.state == CamState::Roll);
+    }
+
+    #[test]
+    fn test_only_the_drag_button_ends_the_drag() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.handle_mouse_move(400.0, 300.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+
+        // Brushing a thumb button in the middle of a tumble leaves the tumble alone
+        camera.handle_mouse_input(MouseButton::Back, ButtonState::Pressed);
+        camera.handle_mouse_input(MouseButton::Back, ButtonState::Released);
+        camera.handle_mouse_input(MouseButton::Right, ButtonState::Released);
+        assert!(camera.state == CamState::Tumble);
+
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+        assert!(camera.state == CamState::Idle);
+    }
+
+    #[test]
+    fn test_touch_gestures() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.set_distance(10.0);
+
+        let rotation = camera.rotation;
+        camera.handle_touch(7, TouchPhase::Started, 300.0, 300.0);
+        assert!(camera.state == CamState::Tumble);
+        camera.handle_touch(3, TouchPhase::Started, 500.0, 300.0);
+        assert!(camera.state == CamState::Touch);
+
+        // Spreading the fingers half again as far apart zooms in by that much
+        camera.handle_touch(7, TouchPhase::Moved, 250.0, 300.0);
+        camera.handle_touch(3, TouchPhase::Moved, 550.0, 300.0);
+        close(camera.distance, 10.0 * 200.0 / 300.0, 1e-3);
+        close(camera.rotation.dot(rotation), 1.0, 1e-6);
+
+        // Dragging both fingers pans the same as dragging the mouse between them
+        let mut mouse = make_cam_with_window(800.0, 600.0);
+        mouse.set_distance(camera.distance);
+        mouse.handle_mouse_move(400.0, 300.0);
+        mouse.handle_mouse_input(MouseButton::Right, ButtonState::Pressed);
+        mouse.handle_mouse_move(500.0, 300.0);
+
+        let target = camera.target;
+        // The fingers move one at a time, so small steps keep the spread and the zoom steady
+        for step in 1..101 {
+            let offset = step as f32;
+            camera.handle_touch(7, TouchPhase::Moved, 250.0 + offset, 300.0);
+            camera.handle_touch(3, TouchPhase::Moved, 550.0 + offset, 300.0);
+        }
+        close(camera.distance, mouse.distance, 1e-3);
+        let panned: [f32; 3] = (camera.target - target).into();
+        let expected: [f32; 3] = mouse.target.into();
+        close(&panned, &expected, 1e-2);
+
+        // Twisting a quarter turn rolls a quarter turn
+        camera.handle_touch(3, TouchPhase::Moved, 500.0, 150.0);
+        camera.handle_touch(7, TouchPhase::Moved, 500.0, 450.0);
+        let right: [f32; 3] = camera.rotation.rotate_vector(Vector3::unit_x()).into();
+        close(&right, &[0.0, -1.0, 0.0], 1e-4);
+
+        camera.handle_touch(3, TouchPhase::Ended, 500.0, 150.0);
+        assert!(camera.state == CamState::Tumble);
+        camera.handle_touch(7, TouchPhase::Cancelled, 500.0, 450.0);
+        assert!(camera.state == CamState::Idle);
+
+        // Letting go of a mouse button does not end a touch gesture
+        camera.handle_touch(1, TouchPhase::Started, 300.0, 300.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+        assert!(camera.state == CamState::Tumble);
+
+        // A restore forgets the fingers, so lifting one afterwards does not start a tumble
+        let saved = camera.save_state();
+        camera.handle_touch(2, TouchPhase::Started, 500.0, 300.0);
+        camera.restore_state(&saved);
+        camera.handle_touch(2, TouchPhase::Ended, 500.0, 300.0);
+        assert!(camera.state == CamState::Idle);
+    }
+
+    #[test]
+    fn test_line_scroll() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.set_distance(10.0);
+        camera.handle_input(CameraInput::ScrollLines { lines: 1.0 });
+        close(camera.distance, 10.0 * 0.1f32.exp(), 1e-5);
+
+        camera.set_line_scroll_modifier(0.5);
+        camera.handle_line_scroll(-1.0);
+        close(camera.distance, 10.0 * 0.1f32.exp() * (-0.5f32).exp(), 1e-5);
+
+        // A burst of lines from an accelerated wheel still zooms in, with or without smoothing
+        for &time_constant in &[0.0, 100.0] {
+            let mut camera = make_cam_with_window(800.0, 600.0);
+            camera.set_distance(10.0);
+            camera.set_zoom_time_constant(time_constant);
+            camera.handle_line_scroll(-20.0);
+            for _ in 0..100 {
+                camera.update(16.0, 800.0, 600.0);
+            }
+            close(camera.distance, 10.0 * (-2.0f32).exp(), 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_frame_sphere_fits_view() {
+        for &(width, height) in &[(800.0, 400.0), (400.0, 800.0)] {
+            let mut camera = make_cam_with_window(width, height);
+            let sphere = Sphere::new(Vector3::new(3.0, -2.0, 7.0), 4.0);
+            camera.frame_sphere(&sphere, 1.0, Easing::Linear);
+            camera.update(1000.0, width, height);
+
+            // The sphere touches the narrower sides of the frustum without crossing them
+            let frustum = camera.get_frustum();
+            let closest = frustum.planes()[..4]
+                .iter()
+                .map(|plane| plane.signed_distance(sphere.center))
+                .fold(f32::INFINITY, f32::min);
+            close(closest, sphere.radius, 1e-3);
+        }
+
+        let mut camera = make_cam_with_window(800.0, 400.0);
+        camera.set_projection_mode(ProjectionMode::Orthographic);
+        let aabb = Aabb::new(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0));
+        camera.frame_aabb(&aabb, 1.5, Easing::Linear);
+        camera.update(1000.0, 800.0, 400.0);
+        close(camera.ortho_extent, 3.0f32.sqrt() * 1.5, 1e-5);
+    }
+
+    #[test]
+    fn test_axis_constrained_tumble() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.handle_mouse_move(350.0, 280.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        camera.set_axis_constraint(Some(AxisConstraint::WorldY));
+
+        // Whatever way we drag, rotating around the world y-axis leaves it where it was
+        let start_rotation = camera.rotation;
+        camera.handle_mouse_move(500.0, 100.0);
+        let delta = camera.rotation * start_rotation.invert();
+        let moved_up: [f32; 3] = delta.rotate_vector(Vector3::unit_y()).into();
+        close(&moved_up, &[0.0, 1.0, 0.0], 1e-4);
+        assert!(delta.s < 0.999);
+
+        // Switching to a camera axis mid tumble keeps the rotation so far
+        let rotation = camera.rotation;
+        camera.set_axis_constraint(Some(AxisConstraint::CameraZ));
+        camera.handle_mouse_move(500.0, 100.0);
+        let rotation: [f32; 4] = rotation.into();
+        let new_rotation: [f32; 4] = camera.rotation.into();
+        close(&new_rotation, &rotation, 1e-4);
+
+        // Rolling around the view axis keeps the view direction
+        let forward = camera.rotation.rotate_vector(Vector3::unit_z());
+        camera.handle_mouse_move(200.0, 400.0);
+        let new_forward: [f32; 3] = camera.rotation.rotate_vector(Vector3::unit_z()).into();
+        let forward: [f32; 3] = forward.into();
+        close(&new_forward, &forward, 1e-4);
+    }
+
+    #[test]
+    fn test_axis_constraint_held_before_tumble() {
+        let mut camera = make_cam_with_window(800.0, 600.0);
+        camera.handle_mouse_move(350.0, 280.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        camera.handle_mouse_move(500.0, 100.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Released);
+
+        // Holding x before pressing should not make the first move jump
+        camera.handle_input(CameraInput::Key {
+            key: Key::Letter('x'),
+            state: ButtonState::Pressed,
+        });
+        camera.handle_mouse_move(300.0, 400.0);
+        camera.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
+        let rotation: [f32; 4] = camera.rotation.into();
+        camera.handle_mouse_move(300.0, 400.0);
+        let new_rotation: [f32; 4] = camera.rotation.into();
+        close(&new_rotation, &rotation, 1e-5);
+    }
 }
@@ -0,0 +1,98 @@
+use super::{CamState, Camera};
+use animation;
+use cgmath::prelude::*;
+use cgmath::Vector3;
+
+// Once the zoom spring is this close to its goal, in log scale, we snap the rest of the way
+const ZOOM_SETTLE_THRESHOLD: f32 = 1.0e-4;
+
+// Pan momentum stops once the target is moving slower than this fraction of the distance to the
+// target per second
+const PAN_STOP_SPEED: f32 = 1.0e-3;
+
+impl Camera {
+    // With zoom smoothing the scroll input only moves the goal, and update chases it. The goal
+    // is kept in log scale so that zooming in and out by the same amount cancels exactly.
+    pub(super) fn add_zoom_goal(&mut self, scale: f32) {
+        if self.zoom_time_constant > 0.0 {
+            self.zoom_goal += scale.ln();
+        } else {
+            self.apply_zoom_scale(scale);
+        }
+    }
+
+    pub(super) fn update_zoom_smoothing(&mut self, elapsed_millis: f32) {
+        if self.zoom_goal == self.zoom_progress {
+            return;
+        }
+
+        let previous = self.zoom_progress;
+        self.zoom_progress = animation::critically_damped_step(
+            self.zoom_progress,
+            self.zoom_goal,
+            &mut self.zoom_velocity,
+            self.zoom_time_constant,
+            elapsed_millis,
+        );
+        if (self.zoom_goal - self.zoom_progress).abs() < ZOOM_SETTLE_THRESHOLD {
+            self.zoom_progress = self.zoom_goal;
+        }
+        self.apply_zoom_scale((self.zoom_progress - previous).exp());
+
+        // Once we get there, start counting from zero again so the goal does not drift off
+        if self.zoom_progress == self.zoom_goal {
+            self.stop_zoom_smoothing();
+        }
+    }
+
+    pub(super) fn stop_zoom_smoothing(&mut self) {
+        self.zoom_goal = 0.0;
+        self.zoom_progress = 0.0;
+        self.zoom_velocity = 0.0;
+        self.zoom_pivot = None;
+    }
+
+    // Remember where the target was at this point in the pan, like the tumble samples for orbit
+    pub(super) fn record_pan_sample(&mut self) {
+        self.pan_samples.push_back((self.pan_duration, self.target));
+
+        let window_start = self.pan_duration - self.orbit_sample_window;
+        while self.pan_samples.len() > 1 && self.pan_samples[1].0 <= window_start {
+            self.pan_samples.pop_front();
+        }
+    }
+
+    // When a pan is let go, measure how fast the target was moving so it can keep going
+    pub(super) fn start_pan_momentum(&mut self) {
+        let window_start = self.pan_duration - self.orbit_sample_window;
+        let reference = self
+            .pan_samples
+            .iter()
+            .rev()
+            .find(|&&(time, _)| time <= window_start)
+            .or_else(|| self.pan_samples.front())
+            .cloned();
+        self.pan_samples.clear();
+
+        self.pan_velocity = match reference {
+            Some((start_time, start_target)) if self.pan_duration > start_time => {
+                (self.target - start_target) / (self.pan_duration - start_time)
+            }
+            _ => Vector3::zero(),
+        };
+    }
+
+    pub(super) fn update_pan_momentum(&mut self, elapsed_millis: f32) {
+        if self.pan_velocity == Vector3::zero() || self.state != CamState::Idle {
+            return;
+        }
+
+        self.target += self.pan_velocity * elapsed_millis;
+        self.pan_velocity *= (-elapsed_millis / self.pan_time_constant).exp();
+
+        let stop_speed = PAN_STOP_SPEED * self.distance / 1000.0;
+        if self.pan_velocity.magnitude() < stop_speed {
+            self.pan_velocity = Vector3::zero();
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use super::{CamState, Camera};
+use cgmath::prelude::*;
+use cgmath::{Quaternion, Rad, Vector2};
+use std::f32;
+
+impl Camera {
+    /// Turn orbiting on and off. While on, letting go of a tumble keeps the camera spinning the
+    /// way it was moving, slowing down until it comes to rest.
+    pub fn toggle_orbit(&mut self) {
+        self.orbit_enabled = !self.orbit_enabled;
+        if !self.orbit_enabled && self.state == CamState::IdleOrbit {
+            self.state = CamState::Idle;
+        }
+    }
+
+    // Remember the rotation at this point in the tumble. Only the last part of the tumble matters
+    // for how fast we were spinning when we let go, so we drop anything older than the sample
+    // window, except for one sample to measure from.
+    pub(super) fn record_tumble_sample(&mut self) {
+        self.tumble_samples
+            .push_back((self.tumble_duration, self.rotation));
+
+        let window_start = self.tumble_duration - self.orbit_sample_window;
+        while self.tumble_samples.len() > 1 && self.tumble_samples[1].0 <= window_start {
+            self.tumble_samples.pop_front();
+        }
+    }
+
+    // When a tumble is let go, measure the angular velocity over the sample window and start
+    // orbiting if it is fast enough
+    pub(super) fn start_orbit(&mut self) -> bool {
+        // The newest sample at or before the start of the window, otherwise the oldest we have.
+        // If the mouse was held still before letting go, this is the same as the current rotation.
+        let window_start = self.tumble_duration - self.orbit_sample_window;
+        let reference = self
+            .tumble_samples
+            .iter()
+            .rev()
+            .find(|&&(time, _)| time <= window_start)
+            .or_else(|| self.tumble_samples.front())
+            .cloned();
+        self.tumble_samples.clear();
+
+        let (start_time, start_rotation) = match reference {
+            Some(sample) => sample,
+            None => return false,
+        };
+        let elapsed_millis = self.tumble_duration - start_time;
+        if elapsed_millis <= 0.0 {
+            return false;
+        }
+
+        // The turntable keeps going as yaw and pitch, so the horizon stays level
+        self.orbit_turntable = self.state == CamState::Turntable;
+        if self.orbit_turntable {
+            let (start_yaw, start_pitch) = self.turntable_angles(start_rotation);
+            let (yaw, pitch) = self.turntable_angles(self.rotation);
+            let turn = f32::consts::PI * 2.0;
+            let yaw_delta = (yaw - start_yaw + f32::consts::PI).rem_euclid(turn) - f32::consts::PI;
+            let delta = Vector2::new(yaw_delta, pitch - start_pitch);
+            let angle = delta.magnitude();
+            if angle <= f32::EPSILON {
+                return false;
+            }
+
+            self.orbit_axis = (delta / angle).extend(0.0);
+            self.orbit_velocity = (angle * 1000.0) / elapsed_millis;
+            return self.orbit_velocity > self.orbit_stop_speed;
+        }
+
+        // The tumble is applied in the camera's frame, so that is where we measure it too
+        let mut delta = start_rotation.invert() * self.rotation;
+        if delta.s < 0.0 {
+            delta = -delta;
+        }
+        let axis_length = delta.v.magnitude();
+        if axis_length <= f32::EPSILON {
+            return false;
+        }
+
+        let angle = 2.0 * axis_length.atan2(delta.s);
+        self.orbit_axis = delta.v / axis_length;
+        self.orbit_velocity = (angle * 1000.0) / elapsed_millis;
+        self.orbit_velocity > self.orbit_stop_speed
+    }
+
+    // Keep spinning about the measured axis, with the speed decaying exponentially. A turntable
+    // orbit steps the yaw and pitch instead, clamping the pitch on every step.
+    pub(super) fn update_orbit(&mut self, elapsed_millis: f32) {
+        let angle = self.orbit_velocity * (elapsed_millis / 1000.0);
+        if self.orbit_turntable {
+            let (yaw, pitch) = self.turntable_angles(self.rotation);
+            self.rotation = self.turntable_from_angles(
+                yaw + self.orbit_axis.x * angle,
+                pitch + self.orbit_axis.y * angle,
+            );
+        } else {
+            let step = Quaternion::from_axis_angle(self.orbit_axis, Rad(angle));
+            self.rotation = (self.rotation * step).normalize();
+        }
+
+        if self.orbit_damping > 0.0 {
+            self.orbit_velocity *= (-elapsed_millis / self.orbit_damping).exp();
+        }
+        if self.orbit_velocity <= self.orbit_stop_speed {
+            self.state = CamState::Idle;
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use super::{CamState, Camera};
+use cgmath::prelude::*;
+use cgmath::Vector3;
+use path::CameraPath;
+
+/// What happens when path playback reaches the end of the path
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaybackMode {
+    /// Stop on the last keyframe
+    Once,
+    /// Jump back to the start and keep going
+    Loop,
+    /// Turn around and play back toward the start, then forwards again
+    PingPong,
+}
+
+impl Camera {
+    /// Start playing a path from its first keyframe, it is played back through `update`.
+    /// Tumbling, panning or starting a transition stops the playback.
+    pub fn play_path(&mut self, path: CameraPath, mode: PlaybackMode) {
+        if path.keyframes().is_empty() {
+            return;
+        }
+
+        self.state = CamState::Path;
+        self.stop_zoom_smoothing();
+        self.pan_velocity = Vector3::zero();
+        self.orbit_velocity = 0.0;
+
+        self.path_time = if self.path_speed < 0.0 {
+            path.end_time()
+        } else {
+            path.start_time()
+        };
+        self.path_direction = 1.0;
+        self.path_paused = false;
+        self.path_mode = mode;
+        self.path = Some(path);
+        self.apply_path_time();
+    }
+
+    /// Stop playing the path, leaving the camera where it is
+    pub fn stop_path(&mut self) {
+        if self.state == CamState::Path {
+            self.state = CamState::Idle;
+        }
+    }
+
+    pub fn is_playing_path(&self) -> bool {
+        self.state == CamState::Path
+    }
+
+    /// Pause or resume the playback, the camera holds still while paused but can still be scrubbed
+    pub fn set_path_paused(&mut self, paused: bool) {
+        self.path_paused = paused;
+    }
+
+    /// The current time along the path in milliseconds
+    pub fn path_time(&self) -> f32 {
+        self.path_time
+    }
+
+    /// Jump to a time along the path, for scrubbing through it with a slider
+    pub fn scrub_path(&mut self, time: f32) {
+        if self.state != CamState::Path {
+            return;
+        }
+        if let Some(ref path) = self.path {
+            self.path_time = time.clamp(path.start_time(), path.end_time());
+        }
+        self.apply_path_time();
+    }
+
+    pub(super) fn update_path(&mut self, elapsed_millis: f32) {
+        if self.path_paused {
+            return;
+        }
+
+        let (start, end) = match self.path {
+            Some(ref path) => (path.start_time(), path.end_time()),
+            None => {
+                self.state = CamState::Idle;
+                return;
+            }
+        };
+        let length = end - start;
+        let time = self.path_time + elapsed_millis * self.path_speed * self.path_direction;
+
+        self.path_time = if time >= start && time <= end {
+            time
+        } else if length <= 0.0 {
+            start
+        } else {
+            match self.path_mode {
+                PlaybackMode::Once => {
+                    self.state = CamState::Idle;
+                    time.clamp(start, end)
+                }
+                PlaybackMode::Loop => start + (time - start).rem_euclid(length),
+                PlaybackMode::PingPong => {
+                    // Fold the time back into the path, each pass over an end turns us around
+                    let passes = ((time - start) / length).floor();
+                    let offset = (time - start) - passes * length;
+                    if passes.rem_euclid(2.0) == 1.0 {
+                        self.path_direction = -self.path_direction;
+                        end - offset
+                    } else {
+                        start + offset
+                    }
+                }
+            }
+        };
+        self.apply_path_time();
+    }
+
+    fn apply_path_time(&mut self) {
+        let view = self
+            .path
+            .as_ref()
+            .and_then(|path| path.sample(self.path_time));
+        if let Some(view) = view {
+            self.set_view(&view);
+        }
+    }
+}
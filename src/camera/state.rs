@@ -0,0 +1,99 @@
+use super::{CamState, Camera};
+use cgmath::prelude::*;
+use cgmath::Vector3;
+use perspective::{ClipSpace, DepthMode, ProjectionMode};
+
+/// The version of `CameraState` this library writes. States from older versions can still be
+/// restored, fields they do not have keep their default values. States from newer versions are
+/// restored too, the fields this version does not know about are ignored when deserializing.
+pub const CAMERA_STATE_VERSION: u32 = 1;
+
+/// The pose and settings of a camera, for saving it in project files and restoring it later.
+/// Gestures and animations that are in progress are not part of the state. Vectors are stored as
+/// plain arrays and rotations as [w, x, y, z] so the format does not depend on cgmath.
+///
+/// With the serde feature this can be serialized, and missing fields fall back to their defaults.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CameraState {
+    pub version: u32,
+    pub target: [f32; 3],
+    pub rotation: [f32; 4],
+    pub distance: f32,
+    pub field_of_view: f32,
+    pub near: f32,
+    pub far: f32,
+    pub projection_mode: ProjectionMode,
+    pub ortho_extent: f32,
+    pub depth_mode: DepthMode,
+    pub clip_space: ClipSpace,
+    pub up: [f32; 3],
+    pub default_target: [f32; 3],
+    pub default_rotation: [f32; 4],
+    pub default_distance: f32,
+    pub default_transition_duration: f32,
+    pub scroll_modifier: f32,
+    pub line_scroll_modifier: f32,
+}
+
+impl Default for CameraState {
+    fn default() -> CameraState {
+        Camera::new().save_state()
+    }
+}
+
+impl Camera {
+    /// Take a snapshot of the camera's pose and settings
+    pub fn save_state(&self) -> CameraState {
+        CameraState {
+            version: CAMERA_STATE_VERSION,
+            target: self.target.into(),
+            rotation: self.rotation.into(),
+            distance: self.distance,
+            field_of_view: self.field_of_view,
+            near: self.near,
+            far: self.far,
+            projection_mode: self.projection_mode,
+            ortho_extent: self.ortho_extent,
+            depth_mode: self.depth_mode,
+            clip_space: self.clip_space,
+            up: self.up.into(),
+            default_target: self.default_target.into(),
+            default_rotation: self.default_rotation.into(),
+            default_distance: self.default_distance,
+            default_transition_duration: self.default_transition_duration,
+            scroll_modifier: self.scroll_modifier,
+            line_scroll_modifier: self.line_scroll_modifier,
+        }
+    }
+
+    /// Go back to a saved state. Anything in progress, like a drag, an orbit or a transition, is
+    /// stopped.
+    pub fn restore_state(&mut self, state: &CameraState) {
+        self.state = CamState::Idle;
+        self.orbit_velocity = 0.0;
+        self.pan_velocity = Vector3::zero();
+        self.stop_zoom_smoothing();
+        self.fly_keys = [false; 6];
+        self.touches.clear();
+
+        self.target = state.target.into();
+        self.rotation = state.rotation.into();
+        self.distance = state.distance;
+        self.field_of_view = state.field_of_view;
+        self.near = state.near;
+        self.far = state.far;
+        self.projection_mode = state.projection_mode;
+        self.ortho_extent = state.ortho_extent;
+        self.depth_mode = state.depth_mode;
+        self.clip_space = state.clip_space;
+        self.up = state.up.into();
+        self.default_target = state.default_target.into();
+        self.default_rotation = state.default_rotation.into();
+        self.default_distance = state.default_distance;
+        self.default_transition_duration = state.default_transition_duration;
+        self.scroll_modifier = state.scroll_modifier;
+        self.line_scroll_modifier = state.line_scroll_modifier;
+    }
+}
@@ -0,0 +1,105 @@
+use super::{CamState, Camera};
+use cgmath::prelude::*;
+use cgmath::Vector2;
+use input::DragAction;
+
+/// Where a touch is in its life, the same as the windowing libraries
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TouchPhase {
+    Started,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+impl Camera {
+    /// Handle a touch point, in pixels with the origin in the top left of the window. Each finger
+    /// is tracked by its id. One finger tumbles, and two fingers pinch to zoom, drag to pan and
+    /// twist to roll all at once. With more than two fingers the first two by id are used.
+    pub fn handle_touch(&mut self, id: u64, phase: TouchPhase, x: f32, y: f32) {
+        // While flying the mouse is only used for looking around, and touch the same
+        if self.state == CamState::Fly {
+            return;
+        }
+
+        let position = Vector2::new(x, y);
+        match phase {
+            TouchPhase::Started => {
+                self.touches.insert(id, position);
+                self.start_touch_gesture();
+            }
+            TouchPhase::Moved => {
+                if !self.touches.contains_key(&id) {
+                    return;
+                }
+                let last = self.touch_pair();
+                self.touches.insert(id, position);
+
+                if self.touches.len() == 1 {
+                    self.handle_mouse_move(x, y);
+                } else if let (Some(last), Some(current)) = (last, self.touch_pair()) {
+                    self.move_two_fingers(last, current);
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                if self.touches.remove(&id).is_some() {
+                    self.start_touch_gesture();
+                }
+            }
+        }
+    }
+
+    // Whenever a finger goes down or comes up the gesture starts over with the fingers that are
+    // left, so lifting one of two fingers goes back to tumbling from where that finger is
+    fn start_touch_gesture(&mut self) {
+        let first = self.touches.values().next().cloned();
+        match (self.touches.len(), first) {
+            (1, Some(position)) => {
+                self.end_drag();
+                self.prev_mouse_coords = position;
+                self.start_drag(DragAction::Tumble);
+            }
+            (0, _) => self.end_drag(),
+            _ => {
+                self.state = CamState::Touch;
+                self.zoom_pivot = None;
+            }
+        }
+    }
+
+    fn touch_pair(&self) -> Option<(Vector2<f32>, Vector2<f32>)> {
+        let mut positions = self.touches.values();
+        match (positions.next(), positions.next()) {
+            (Some(&first), Some(&second)) => Some((first, second)),
+            _ => None,
+        }
+    }
+
+    fn move_two_fingers(
+        &mut self,
+        last: (Vector2<f32>, Vector2<f32>),
+        current: (Vector2<f32>, Vector2<f32>),
+    ) {
+        if self.state != CamState::Touch {
+            return;
+        }
+
+        // Twisting turns the scene with the line between the fingers
+        let last_span = self.mouse_to_screen(last.1) - self.mouse_to_screen(last.0);
+        let span = self.mouse_to_screen(current.1) - self.mouse_to_screen(current.0);
+        self.roll_screen(last_span, span);
+
+        // Spreading the fingers apart zooms in by the same ratio
+        let last_spread = last_span.magnitude();
+        let spread = span.magnitude();
+        if last_spread > 0.0 && spread > 0.0 {
+            self.apply_zoom_scale(last_spread / spread);
+        }
+
+        // The point between the fingers pans the same way the mouse does
+        let last_middle = (last.0 + last.1) / 2.0;
+        let middle = (current.0 + current.1) / 2.0;
+        self.target += self.mouse_to_pan_point(last_middle) - self.mouse_to_pan_point(middle);
+        self.prev_mouse_coords = middle;
+    }
+}
@@ -0,0 +1,136 @@
+use super::Camera;
+use animation::Easing;
+use cgmath::prelude::*;
+use cgmath::{Quaternion, Rad, Vector3};
+use std::f32;
+
+/// A snapshot of where the camera is looking from, everything needed to come back to it later
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraView {
+    pub target: Vector3<f32>,
+    pub rotation: Quaternion<f32>,
+    pub distance: f32,
+    pub field_of_view: f32,
+}
+
+/// The standard views that modeling tools snap to. Front looks along -z, right looks along -x,
+/// top looks down from the up axis, and isometric looks at the target from the (1, 1, 1)
+/// direction. They are all relative to the camera's up axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StandardView {
+    Front,
+    Back,
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Isometric,
+}
+
+impl StandardView {
+    /// The camera rotation for this view when the up axis is y
+    pub fn rotation(&self) -> Quaternion<f32> {
+        let quarter_turn = Rad(f32::consts::FRAC_PI_2);
+        match *self {
+            StandardView::Front => Quaternion::one(),
+            StandardView::Back => Quaternion::from_angle_y(Rad(f32::consts::PI)),
+            StandardView::Left => Quaternion::from_angle_y(-quarter_turn),
+            StandardView::Right => Quaternion::from_angle_y(quarter_turn),
+            StandardView::Top => Quaternion::from_angle_x(-quarter_turn),
+            StandardView::Bottom => Quaternion::from_angle_x(quarter_turn),
+            StandardView::Isometric => {
+                // Tilt down until we are looking along the diagonal of a cube
+                let elevation = (1.0 / 2.0f32.sqrt()).atan();
+                Quaternion::from_angle_y(Rad(f32::consts::FRAC_PI_4))
+                    * Quaternion::from_angle_x(Rad(-elevation))
+            }
+        }
+    }
+}
+
+impl Camera {
+    /// Take a snapshot of the current view
+    pub fn current_view(&self) -> CameraView {
+        CameraView {
+            target: self.target,
+            rotation: self.rotation,
+            distance: self.distance,
+            field_of_view: self.field_of_view,
+        }
+    }
+
+    /// Jump straight to a view without animating
+    pub fn set_view(&mut self, view: &CameraView) {
+        self.target = view.target;
+        self.rotation = view.rotation;
+        self.distance = view.distance;
+        self.field_of_view = view.field_of_view;
+    }
+
+    /// Animate to a view, including its field of view
+    pub fn start_view_transition(
+        &mut self,
+        view: &CameraView,
+        transition_duration: f32,
+        easing: Easing,
+    ) {
+        self.start_transition_with_easing(
+            view.target,
+            view.rotation,
+            view.distance,
+            transition_duration,
+            easing,
+        );
+        self.transition_end_field_of_view = view.field_of_view;
+    }
+
+    // Standard views are defined with y up, this takes them to the camera's up axis
+    fn up_frame(&self) -> Quaternion<f32> {
+        Quaternion::from_arc(Vector3::unit_y(), self.up.normalize(), None)
+    }
+
+    /// Animate to one of the standard views, keeping the target and distance. The view we are
+    /// leaving is pushed onto the view stack.
+    pub fn transition_to_standard_view(&mut self, view: StandardView, easing: Easing) {
+        let rotation = self.up_frame() * view.rotation();
+        self.transition_to_rotation(rotation, easing);
+    }
+
+    /// Animate to whichever axis aligned view is closest to the current rotation. There are 24 of
+    /// them, one for each of the six axes we could be looking along times four ways to roll.
+    pub fn snap_to_nearest_view(&mut self, easing: Easing) {
+        let up_frame = self.up_frame();
+        let facings = [
+            StandardView::Front,
+            StandardView::Back,
+            StandardView::Left,
+            StandardView::Right,
+            StandardView::Top,
+            StandardView::Bottom,
+        ];
+
+        // The closer two rotations are, the larger the absolute value of their dot product
+        let mut nearest = self.rotation;
+        let mut nearest_dot = -1.0;
+        for facing in &facings {
+            for roll in 0..4 {
+                let roll = Quaternion::from_angle_z(Rad(roll as f32 * f32::consts::FRAC_PI_2));
+                let rotation = up_frame * facing.rotation() * roll;
+                let dot = self.rotation.dot(rotation).abs();
+                if dot > nearest_dot {
+                    nearest = rotation;
+                    nearest_dot = dot;
+                }
+            }
+        }
+        self.transition_to_rotation(nearest, easing);
+    }
+
+    fn transition_to_rotation(&mut self, rotation: Quaternion<f32>, easing: Easing) {
+        self.push_view();
+        let target = self.target;
+        let distance = self.distance;
+        let duration = self.default_transition_duration;
+        self.start_transition_with_easing(target, rotation, distance, duration, easing);
+    }
+}
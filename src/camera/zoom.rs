@@ -0,0 +1,149 @@
+use super::{CamState, Camera};
+use animation::Easing;
+use cgmath::prelude::*;
+use cgmath::{Vector3, Vector4};
+use perspective::ProjectionMode;
+
+/// What zooming changes in perspective mode. Orthographic zooming always changes the extent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoomMode {
+    /// Move the camera toward or away from the target
+    Dolly,
+    /// Narrow or widen the field of view, between the field of view limits
+    FieldOfView,
+    /// Change the field of view and move the camera to keep the target the same size on screen,
+    /// the vertigo effect
+    DollyZoom,
+}
+
+// Where the camera ends up after zooming, the target moves when zooming toward the cursor
+struct Zoomed {
+    target: Vector3<f32>,
+    distance: f32,
+    field_of_view: f32,
+    ortho_extent: f32,
+}
+
+impl Camera {
+    /// Zoom by the scale over the duration instead of all at once, using the zoom mode. Like
+    /// scrolling, a scale below one zooms in.
+    pub fn start_zoom_transition(&mut self, scale: f32, transition_duration: f32, easing: Easing) {
+        let zoomed = self.zoomed(scale, None);
+        let rotation = self.rotation;
+        self.start_transition_with_easing(
+            zoomed.target,
+            rotation,
+            zoomed.distance,
+            transition_duration,
+            easing,
+        );
+        self.transition_end_field_of_view = zoomed.field_of_view;
+        self.transition_end_ortho_extent = zoomed.ortho_extent;
+        self.transition_dolly_zoom = self.projection_mode == ProjectionMode::Perspective
+            && self.zoom_mode == ZoomMode::DollyZoom;
+    }
+
+    // Everything that zooms goes through here, scroll input as well as zoom smoothing
+    pub(super) fn apply_zoom_scale(&mut self, scale: f32) {
+        let pivot = self.zoom_pivot;
+        let zoomed = self.zoomed(scale, pivot);
+        self.target = zoomed.target;
+        self.distance = zoomed.distance;
+        self.field_of_view = zoomed.field_of_view;
+        self.ortho_extent = zoomed.ortho_extent;
+    }
+
+    // While a dolly zoom transition runs the distance follows the field of view, since
+    // interpolating them separately would let the target change size part way through
+    pub(super) fn hold_dolly_zoom_size(&mut self) {
+        if self.state == CamState::Transition && self.transition_dolly_zoom {
+            let size = self.original_distance * (self.original_field_of_view / 2.0).tan();
+            self.distance = size / (self.field_of_view / 2.0).tan();
+        }
+    }
+
+    fn zoomed(&self, scale: f32, pivot: Option<Vector3<f32>>) -> Zoomed {
+        let mut zoomed = Zoomed {
+            target: self.target,
+            distance: self.distance,
+            field_of_view: self.field_of_view,
+            ortho_extent: self.ortho_extent,
+        };
+
+        match (self.projection_mode, self.zoom_mode) {
+            (ProjectionMode::Orthographic, _) => {
+                zoomed.ortho_extent *= scale;
+                if let Some(pivot) = pivot {
+                    zoomed.target = self.target_across_pivot(pivot, scale);
+                }
+            }
+            (ProjectionMode::Perspective, ZoomMode::Dolly) => {
+                zoomed.distance *= scale;
+                if let Some(pivot) = pivot {
+                    zoomed.target = pivot + (self.target - pivot) * scale;
+                }
+            }
+            (ProjectionMode::Perspective, ZoomMode::FieldOfView) => {
+                // Scaling the tangent scales what is visible at the target's depth, so this feels
+                // the same as dollying for things near the target
+                let half_tan = (self.field_of_view / 2.0).tan();
+                zoomed.field_of_view = self.clamp_field_of_view(2.0 * (half_tan * scale).atan());
+                if let Some(pivot) = pivot {
+                    let actual_scale = (zoomed.field_of_view / 2.0).tan() / half_tan;
+                    zoomed.target = self.target_across_pivot(pivot, actual_scale);
+                }
+            }
+            (ProjectionMode::Perspective, ZoomMode::DollyZoom) => {
+                // The target's size on screen goes with distance * tan(fov / 2), so when the
+                // field of view runs into a limit the distance is worked out from it
+                let size = self.distance * (self.field_of_view / 2.0).tan();
+                let half_tan = size / (self.distance * scale);
+                zoomed.field_of_view = self.clamp_field_of_view(2.0 * half_tan.atan());
+                zoomed.distance = size / (zoomed.field_of_view / 2.0).tan();
+            }
+        }
+
+        zoomed
+    }
+
+    fn clamp_field_of_view(&self, field_of_view: f32) -> f32 {
+        field_of_view.clamp(self.min_field_of_view, self.max_field_of_view)
+    }
+
+    // Move the target across the screen so the pivot stays put when what is visible at the
+    // target's depth scales, with the camera's depth left alone
+    fn target_across_pivot(&self, pivot: Vector3<f32>, scale: f32) -> Vector3<f32> {
+        let forward = self.rotation.rotate_vector(-Vector3::unit_z());
+        let offset = pivot - self.target;
+        let across = offset - forward * offset.dot(forward);
+        self.target + across * (1.0 - scale)
+    }
+
+    // Find the world point under the mouse that zooming should hold still. With a depth from the
+    // application we unproject it, otherwise we use where the mouse ray crosses the plane through
+    // the target that faces the camera.
+    pub(super) fn zoom_pivot(&self, depth: Option<f32>) -> Vector3<f32> {
+        let ndc = self.pixel_to_ndc(self.prev_mouse_coords);
+        let unprojected = depth.and_then(|depth| {
+            let inverse = self.get_clipspace_transform().invert()?;
+            let point = inverse * Vector4::new(ndc.x, ndc.y, depth, 1.0);
+            let point = point.truncate() / point.w;
+            if point.x.is_finite() && point.y.is_finite() && point.z.is_finite() {
+                Some(point)
+            } else {
+                None
+            }
+        });
+
+        unprojected.unwrap_or_else(|| {
+            let ray = self.pixel_to_ray(self.prev_mouse_coords.x, self.prev_mouse_coords.y);
+            let forward = self.rotation.rotate_vector(-Vector3::unit_z());
+            let along = ray.direction.dot(forward);
+            if along > f32::EPSILON {
+                ray.at((self.target - ray.origin).dot(forward) / along)
+            } else {
+                self.target
+            }
+        })
+    }
+}
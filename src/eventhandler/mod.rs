@@ -1,48 +1,185 @@
 use camera::*;
 use glutin;
+use input::{CameraInput, Key, Modifiers};
 
 /// This function implements the suggested handling of mouse and keyboard input. Feel free to
 /// intercept RecievedCharacter events if you do not want to use those shortcuts
 pub fn camera_event_handler<'a>(cam: &'a mut Camera, event: glutin::Event) {
-    match event {
-        glutin::Event::WindowEvent { event, .. } => match event {
+    for input in glutin_camera_inputs(&event) {
+        cam.handle_input(input);
+    }
+}
+
+/// Turn a glutin event into camera inputs. Glutin sends the modifiers along with each event, so
+/// those come first as their own input.
+pub fn glutin_camera_inputs(event: &glutin::Event) -> Vec<CameraInput> {
+    let mut inputs = Vec::new();
+    match *event {
+        glutin::Event::DeviceEvent {
+            event: glutin::DeviceEvent::MouseMotion { delta: (x, y) },
+            ..
+        } => {
+            inputs.push(CameraInput::MouseMotion {
+                dx: x as f32,
+                dy: y as f32,
+            });
+        }
+        glutin::Event::WindowEvent { ref event, .. } => match *event {
             glutin::WindowEvent::MouseWheel {
                 delta: glutin::MouseScrollDelta::PixelDelta(_, y),
+                modifiers,
+                ..
+            } => {
+                inputs.push(CameraInput::Modifiers(glutin_modifiers(modifiers)));
+                inputs.push(CameraInput::Scroll { pixel_delta: y });
+            }
+            glutin::WindowEvent::MouseWheel {
+                delta: glutin::MouseScrollDelta::LineDelta(_, y),
+                modifiers,
                 ..
             } => {
-                cam.handle_scroll(y);
+                inputs.push(CameraInput::Modifiers(glutin_modifiers(modifiers)));
+                inputs.push(CameraInput::ScrollLines { lines: y });
             }
             glutin::WindowEvent::CursorMoved {
                 position: (x, y), ..
             } => {
-                cam.handle_mouse_move(x as f32, y as f32);
+                inputs.push(CameraInput::CursorMoved {
+                    x: x as f32,
+                    y: y as f32,
+                });
             }
-            glutin::WindowEvent::MouseInput { state, button, .. } => match (state, button) {
-                (glutin::ElementState::Pressed, glutin::MouseButton::Left) => {
-                    cam.handle_mouse_input(MouseButton::Left, ButtonState::Pressed);
-                }
-                (glutin::ElementState::Pressed, glutin::MouseButton::Right) => {
-                    cam.handle_mouse_input(MouseButton::Right, ButtonState::Pressed);
-                }
-                (glutin::ElementState::Released, glutin::MouseButton::Left) => {
-                    cam.handle_mouse_input(MouseButton::Left, ButtonState::Released);
-                }
-                (glutin::ElementState::Released, glutin::MouseButton::Right) => {
-                    cam.handle_mouse_input(MouseButton::Right, ButtonState::Released);
-                }
-                _ => (),
-            },
-            glutin::WindowEvent::ReceivedCharacter(c) => match c {
-                's' => {
-                    cam.set_current_as_default();
-                }
-                'd' => {
-                    cam.transition_to_default();
+            glutin::WindowEvent::MouseInput {
+                state,
+                button,
+                modifiers,
+                ..
+            } => {
+                let button = match button {
+                    glutin::MouseButton::Left => MouseButton::Left,
+                    glutin::MouseButton::Right => MouseButton::Right,
+                    glutin::MouseButton::Middle => MouseButton::Middle,
+                    glutin::MouseButton::Other(number) => MouseButton::Other(u16::from(number)),
+                };
+                inputs.push(CameraInput::Modifiers(glutin_modifiers(modifiers)));
+                inputs.push(CameraInput::MouseButton {
+                    button,
+                    state: glutin_button_state(state),
+                });
+            }
+            glutin::WindowEvent::KeyboardInput {
+                input:
+                    glutin::KeyboardInput {
+                        state,
+                        virtual_keycode: Some(key),
+                        modifiers,
+                        ..
+                    },
+                ..
+            } => {
+                inputs.push(CameraInput::Modifiers(glutin_modifiers(modifiers)));
+                if let Some(key) = glutin_key(key) {
+                    inputs.push(CameraInput::Key {
+                        key,
+                        state: glutin_button_state(state),
+                    });
                 }
-                _ => (),
-            },
+            }
+            glutin::WindowEvent::Touch(glutin::Touch {
+                phase,
+                location: (x, y),
+                id,
+                ..
+            }) => {
+                let phase = match phase {
+                    glutin::TouchPhase::Started => TouchPhase::Started,
+                    glutin::TouchPhase::Moved => TouchPhase::Moved,
+                    glutin::TouchPhase::Ended => TouchPhase::Ended,
+                    glutin::TouchPhase::Cancelled => TouchPhase::Cancelled,
+                };
+                inputs.push(CameraInput::Touch {
+                    id,
+                    phase,
+                    x: x as f32,
+                    y: y as f32,
+                });
+            }
+            glutin::WindowEvent::ReceivedCharacter(c) => {
+                inputs.push(CameraInput::Character(c));
+            }
             _ => (),
         },
         _ => (),
     }
+    inputs
+}
+
+fn glutin_button_state(state: glutin::ElementState) -> ButtonState {
+    match state {
+        glutin::ElementState::Pressed => ButtonState::Pressed,
+        glutin::ElementState::Released => ButtonState::Released,
+    }
+}
+
+fn glutin_modifiers(modifiers: glutin::ModifiersState) -> Modifiers {
+    Modifiers {
+        shift: modifiers.shift,
+        ctrl: modifiers.ctrl,
+        alt: modifiers.alt,
+        logo: modifiers.logo,
+    }
+}
+
+fn glutin_key(key: glutin::VirtualKeyCode) -> Option<Key> {
+    use glutin::VirtualKeyCode::*;
+    let key = match key {
+        A => Key::Letter('a'),
+        B => Key::Letter('b'),
+        C => Key::Letter('c'),
+        D => Key::Letter('d'),
+        E => Key::Letter('e'),
+        F => Key::Letter('f'),
+        G => Key::Letter('g'),
+        H => Key::Letter('h'),
+        I => Key::Letter('i'),
+        J => Key::Letter('j'),
+        K => Key::Letter('k'),
+        L => Key::Letter('l'),
+        M => Key::Letter('m'),
+        N => Key::Letter('n'),
+        O => Key::Letter('o'),
+        P => Key::Letter('p'),
+        Q => Key::Letter('q'),
+        R => Key::Letter('r'),
+        S => Key::Letter('s'),
+        T => Key::Letter('t'),
+        U => Key::Letter('u'),
+        V => Key::Letter('v'),
+        W => Key::Letter('w'),
+        X => Key::Letter('x'),
+        Y => Key::Letter('y'),
+        Z => Key::Letter('z'),
+        Key0 => Key::Digit(0),
+        Key1 => Key::Digit(1),
+        Key2 => Key::Digit(2),
+        Key3 => Key::Digit(3),
+        Key4 => Key::Digit(4),
+        Key5 => Key::Digit(5),
+        Key6 => Key::Digit(6),
+        Key7 => Key::Digit(7),
+        Key8 => Key::Digit(8),
+        Key9 => Key::Digit(9),
+        Numpad0 => Key::Numpad(0),
+        Numpad1 => Key::Numpad(1),
+        Numpad2 => Key::Numpad(2),
+        Numpad3 => Key::Numpad(3),
+        Numpad4 => Key::Numpad(4),
+        Numpad5 => Key::Numpad(5),
+        Numpad6 => Key::Numpad(6),
+        Numpad7 => Key::Numpad(7),
+        Numpad8 => Key::Numpad(8),
+        Numpad9 => Key::Numpad(9),
+        _ => return None,
+    };
+    Some(key)
 }
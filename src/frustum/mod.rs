@@ -0,0 +1,261 @@
+use bounds::{Aabb, Sphere};
+use camera::Camera;
+use cgmath::prelude::*;
+use cgmath::{Matrix4, Vector3, Vector4};
+use perspective::{ClipSpace, DepthMode, DepthRange, YDirection};
+use std::f32;
+
+/// A plane in world coordinates. Points on the side the normal points towards have a positive
+/// signed distance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    pub normal: Vector3<f32>,
+    pub distance: f32,
+}
+
+impl Plane {
+    // Planes pulled out of a transform come as a 4 vector that is not normalized. A degenerate
+    // plane, like the far plane of an infinite frustum, has nothing on its outside.
+    fn from_coefficients(coefficients: Vector4<f32>) -> Plane {
+        let normal = coefficients.truncate();
+        let length = normal.magnitude();
+        if length <= f32::EPSILON {
+            Plane {
+                normal: Vector3::zero(),
+                distance: f32::INFINITY,
+            }
+        } else {
+            Plane {
+                normal: normal / length,
+                distance: coefficients.w / length,
+            }
+        }
+    }
+
+    /// How far a point is in front of the plane, negative when it is behind
+    pub fn signed_distance(&self, point: Vector3<f32>) -> f32 {
+        self.normal.dot(point) + self.distance
+    }
+}
+
+/// The result of testing a bounding volume against a frustum
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Containment {
+    Inside,
+    Intersecting,
+    Outside,
+}
+
+/// The viewing volume of a camera in world coordinates, described by six planes whose normals
+/// point inwards, and the eight corners where those planes meet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frustum {
+    planes: [Plane; 6],
+    corners: [Vector3<f32>; 8],
+}
+
+impl Frustum {
+    /// Build the frustum of the view the camera is currently rendering
+    pub fn from_camera(camera: &Camera) -> Frustum {
+        Frustum::from_transform(
+            camera.get_clipspace_transform(),
+            *camera.clip_space(),
+            *camera.depth_mode(),
+        )
+    }
+
+    /// Build a frustum from any world to clip space transform, for example one made with
+    /// `perspective_transform`. The clip space and depth mode the transform was made with are
+    /// needed to tell which planes are which. For the infinite depth modes there is no far
+    /// plane, so it is replaced by a plane that everything is inside of, and the far corners are
+    /// pushed out as far as an f32 can go along the edges of the frustum.
+    pub fn from_transform(
+        transform: Matrix4<f32>,
+        clip_space: ClipSpace,
+        depth_mode: DepthMode,
+    ) -> Frustum {
+        // This is the Gribb and Hartmann method, each clip space boundary like -w <= x is a
+        // plane in world space made from a combination of the rows of the transform
+        let x = transform.row(0);
+        let y = transform.row(1);
+        let z = transform.row(2);
+        let w = transform.row(3);
+
+        let (mut bottom, mut top) = (w + y, w - y);
+        if clip_space.y_direction == YDirection::Down {
+            ::std::mem::swap(&mut bottom, &mut top);
+        }
+
+        let min_depth = match clip_space.depth_range {
+            DepthRange::NegativeOneToOne => w + z,
+            DepthRange::ZeroToOne => z,
+        };
+        let max_depth = w - z;
+        let (near, far) = match depth_mode {
+            DepthMode::Standard | DepthMode::Infinite => (min_depth, max_depth),
+            DepthMode::Reversed | DepthMode::InfiniteReversed => (max_depth, min_depth),
+        };
+
+        let planes = [
+            Plane::from_coefficients(w + x),
+            Plane::from_coefficients(w - x),
+            Plane::from_coefficients(bottom),
+            Plane::from_coefficients(top),
+            Plane::from_coefficients(near),
+            Plane::from_coefficients(far),
+        ];
+
+        // The corners are the corners of the clip space cube run backwards through the transform
+        let inverse = transform.invert().unwrap_or_else(Matrix4::identity);
+        let (near_depth, far_depth) = clip_space.near_far_depths(depth_mode);
+        let y_sign = match clip_space.y_direction {
+            YDirection::Up => 1.0,
+            YDirection::Down => -1.0,
+        };
+        let mut corners = [Vector3::zero(); 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let ndc_x = if i & 1 == 0 { -1.0 } else { 1.0 };
+            let ndc_y = if i & 2 == 0 { -y_sign } else { y_sign };
+            let ndc_z = if i & 4 == 0 { near_depth } else { far_depth };
+            let point = inverse * Vector4::new(ndc_x, ndc_y, ndc_z, 1.0);
+            *corner = if point.w.abs() <= f32::EPSILON {
+                point.truncate().normalize() * f32::MAX
+            } else {
+                point.truncate() / point.w
+            };
+        }
+
+        Frustum { planes, corners }
+    }
+
+    /// The planes in the order left, right, bottom, top, near, far
+    pub fn planes(&self) -> &[Plane; 6] {
+        &self.planes
+    }
+
+    /// The corners ordered with left to right changing fastest, then bottom to top, then near to
+    /// far. So the first corner is near bottom left and the last is far top right.
+    pub fn corners(&self) -> &[Vector3<f32>; 8] {
+        &self.corners
+    }
+
+    pub fn contains_point(&self, point: Vector3<f32>) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(point) >= 0.0)
+    }
+
+    pub fn test_sphere(&self, sphere: &Sphere) -> Containment {
+        let mut containment = Containment::Inside;
+        for plane in &self.planes {
+            let distance = plane.signed_distance(sphere.center);
+            if distance < -sphere.radius {
+                return Containment::Outside;
+            } else if distance < sphere.radius {
+                containment = Containment::Intersecting;
+            }
+        }
+        containment
+    }
+
+    pub fn test_aabb(&self, aabb: &Aabb) -> Containment {
+        let center = aabb.center();
+        let half_extents = aabb.half_extents();
+        let mut containment = Containment::Inside;
+        for plane in &self.planes {
+            // The box reaches furthest along the normal by this much from its center
+            let radius = half_extents.x * plane.normal.x.abs()
+                + half_extents.y * plane.normal.y.abs()
+                + half_extents.z * plane.normal.z.abs();
+            let distance = plane.signed_distance(center);
+            if distance < -radius {
+                return Containment::Outside;
+            } else if distance < radius {
+                containment = Containment::Intersecting;
+            }
+        }
+        containment
+    }
+
+    /// Collect the indices of the spheres that are at least partly inside the frustum. The
+    /// visible list is cleared first, so it can be reused across frames without reallocating.
+    pub fn cull_spheres(&self, spheres: &[Sphere], visible: &mut Vec<usize>) {
+        visible.clear();
+        visible.extend(
+            spheres
+                .iter()
+                .enumerate()
+                .filter(|&(_, sphere)| self.test_sphere(sphere) != Containment::Outside)
+                .map(|(i, _)| i),
+        );
+    }
+
+    /// Collect the indices of the boxes that are at least partly inside the frustum. The
+    /// visible list is cleared first, so it can be reused across frames without reallocating.
+    pub fn cull_aabbs(&self, aabbs: &[Aabb], visible: &mut Vec<usize>) {
+        visible.clear();
+        visible.extend(
+            aabbs
+                .iter()
+                .enumerate()
+                .filter(|&(_, aabb)| self.test_aabb(aabb) != Containment::Outside)
+                .map(|(i, _)| i),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert::*;
+    use perspective::perspective_transform;
+
+    // A symmetric frustum with a right angle field of view, looking down -z from the origin
+    fn make_frustum(clip_space: ClipSpace) -> Frustum {
+        let transform = clip_space.convert(perspective_transform(1.0, 10.0, -1.0, 1.0, -1.0, 1.0));
+        Frustum::from_transform(transform, clip_space, DepthMode::Standard)
+    }
+
+    #[test]
+    fn test_planes_and_corners() {
+        for &clip_space in &[ClipSpace::opengl(), ClipSpace::vulkan()] {
+            let frustum = make_frustum(clip_space);
+
+            let near: [f32; 3] = frustum.planes()[4].normal.into();
+            close(&near, &[0.0, 0.0, -1.0], 1e-5);
+            close(frustum.planes()[4].distance, -1.0, 1e-5);
+            let top: [f32; 3] = frustum.planes()[3].normal.into();
+            let expected: [f32; 3] = Vector3::new(0.0, -1.0, -1.0).normalize().into();
+            close(&top, &expected, 1e-5);
+
+            let far_corner: [f32; 3] = frustum.corners()[7].into();
+            close(&far_corner, &[10.0, 10.0, -10.0], 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_containment() {
+        let frustum = make_frustum(ClipSpace::opengl());
+        assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -5.0)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, 5.0)));
+
+        let inside = Sphere::new(Vector3::new(0.0, 0.0, -5.0), 1.0);
+        let straddling = Sphere::new(Vector3::new(0.0, 0.0, -10.0), 1.0);
+        let outside = Sphere::new(Vector3::new(20.0, 0.0, -5.0), 1.0);
+        assert_eq!(frustum.test_sphere(&inside), Containment::Inside);
+        assert_eq!(frustum.test_sphere(&straddling), Containment::Intersecting);
+        assert_eq!(frustum.test_sphere(&outside), Containment::Outside);
+
+        let aabbs = [
+            Aabb::new(Vector3::new(-1.0, -1.0, -6.0), Vector3::new(1.0, 1.0, -4.0)),
+            Aabb::new(Vector3::new(5.0, 5.0, -11.0), Vector3::new(7.0, 7.0, -9.0)),
+            Aabb::new(Vector3::new(-1.0, -1.0, 1.0), Vector3::new(1.0, 1.0, 2.0)),
+        ];
+        assert_eq!(frustum.test_aabb(&aabbs[0]), Containment::Inside);
+        assert_eq!(frustum.test_aabb(&aabbs[1]), Containment::Intersecting);
+
+        let mut visible = vec![2];
+        frustum.cull_aabbs(&aabbs, &mut visible);
+        assert_eq!(visible, vec![0, 1]);
+    }
+}
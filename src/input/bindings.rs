@@ -0,0 +1,259 @@
+use super::{Key, Modifiers};
+use camera::{AxisConstraint, FlyDirection, MouseButton, StandardView};
+use std::collections::HashMap;
+
+/// What dragging with a mouse button does
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DragAction {
+    /// Rotate around the target, with the orbit style
+    Tumble,
+    /// Move the target across the screen
+    Pan,
+    /// Zoom with vertical motion, the same way scrolling does
+    Zoom,
+    /// Roll around the view direction by circling the mouse around the middle of the window
+    Roll,
+}
+
+/// What a key does. Flying and the axis constraints last while the key is held, everything else
+/// happens when the key is pressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyAction {
+    Fly(FlyDirection),
+    ConstrainAxis(AxisConstraint),
+    ToggleFly,
+    /// Make the current view the default
+    SetDefault,
+    /// Go back to the default view
+    ResetToDefault,
+    /// Go back to the previous view on the view stack
+    TransitionBack,
+    SaveBookmark(u8),
+    RecallBookmark(u8),
+    StandardView(StandardView),
+    SnapToNearestView,
+}
+
+/// Maps mouse buttons, keys and typed characters to what they do to the camera. Buttons and keys
+/// are bound together with the modifiers that have to be held. When nothing is bound to the exact
+/// modifiers being held, the binding without any modifiers is used, so shift can boost flying
+/// without needing its own fly bindings. Keys only fall back like this when they have no bindings
+/// with modifiers at all, so a key that saves with control doesn't recall with shift.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Bindings {
+    drags: HashMap<(MouseButton, Modifiers), DragAction>,
+    keys: HashMap<(Key, Modifiers), KeyAction>,
+    characters: HashMap<char, KeyAction>,
+}
+
+const NONE: Modifiers = Modifiers {
+    shift: false,
+    ctrl: false,
+    alt: false,
+    logo: false,
+};
+
+const SHIFT: Modifiers = Modifiers {
+    shift: true,
+    ..NONE
+};
+
+const CTRL: Modifiers = Modifiers { ctrl: true, ..NONE };
+
+const ALT: Modifiers = Modifiers { alt: true, ..NONE };
+
+const CTRL_ALT: Modifiers = Modifiers {
+    ctrl: true,
+    alt: true,
+    ..NONE
+};
+
+impl Bindings {
+    /// No bindings at all
+    pub fn empty() -> Bindings {
+        Bindings::default()
+    }
+
+    /// The bindings the camera starts with. The left button tumbles, pans with shift and zooms
+    /// with control, so everything works without a right button, and the right and middle
+    /// buttons pan too. WASDEQ fly, holding x, y or z constrains tumbling, the number keys recall
+    /// bookmarks and save them with control, and the number pad goes to the standard views. The
+    /// characters f, s, d and b toggle flying, set the default, go to the default and go back.
+    pub fn aperture() -> Bindings {
+        let mut bindings = Bindings::empty();
+        bindings.bind_drag(MouseButton::Left, NONE, DragAction::Tumble);
+        bindings.bind_drag(MouseButton::Left, SHIFT, DragAction::Pan);
+        bindings.bind_drag(MouseButton::Left, CTRL, DragAction::Zoom);
+        bindings.bind_drag(MouseButton::Right, NONE, DragAction::Pan);
+        bindings.bind_drag(MouseButton::Middle, NONE, DragAction::Pan);
+
+        bindings.bind_fly_keys();
+        let axes = [
+            ('x', AxisConstraint::WorldX, AxisConstraint::CameraX),
+            ('y', AxisConstraint::WorldY, AxisConstraint::CameraY),
+            ('z', AxisConstraint::WorldZ, AxisConstraint::CameraZ),
+        ];
+        for &(letter, world, camera) in &axes {
+            let key = Key::Letter(letter);
+            bindings.bind_key(key, NONE, KeyAction::ConstrainAxis(world));
+            bindings.bind_key(key, SHIFT, KeyAction::ConstrainAxis(camera));
+        }
+        for number in 0..10 {
+            let key = Key::Digit(number);
+            bindings.bind_key(key, NONE, KeyAction::RecallBookmark(number));
+            bindings.bind_key(key, CTRL, KeyAction::SaveBookmark(number));
+        }
+        bindings.bind_numpad_views();
+        bindings.bind_key(
+            Key::Numpad(0),
+            NONE,
+            KeyAction::StandardView(StandardView::Isometric),
+        );
+        bindings.bind_key(Key::Numpad(5), NONE, KeyAction::SnapToNearestView);
+
+        bindings.bind_character('f', KeyAction::ToggleFly);
+        bindings.bind_character('s', KeyAction::SetDefault);
+        bindings.bind_character('d', KeyAction::ResetToDefault);
+        bindings.bind_character('b', KeyAction::TransitionBack);
+        bindings
+    }
+
+    /// Blender's navigation. The middle button tumbles, pans with shift and zooms with control,
+    /// the number pad goes to the standard views, shift f flies with WASDEQ and shift c goes back
+    /// to the default view.
+    pub fn blender() -> Bindings {
+        let mut bindings = Bindings::empty();
+        bindings.bind_drag(MouseButton::Middle, NONE, DragAction::Tumble);
+        bindings.bind_drag(MouseButton::Middle, SHIFT, DragAction::Pan);
+        bindings.bind_drag(MouseButton::Middle, CTRL, DragAction::Zoom);
+
+        bindings.bind_fly_keys();
+        bindings.bind_numpad_views();
+        bindings.bind_key(Key::Letter('f'), SHIFT, KeyAction::ToggleFly);
+        bindings.bind_key(Key::Letter('c'), SHIFT, KeyAction::ResetToDefault);
+        bindings
+    }
+
+    /// Maya's navigation. Holding alt, the left button tumbles, the middle button pans and the
+    /// right button zooms. The a key goes back to the default view and [ goes to the previous
+    /// view.
+    pub fn maya() -> Bindings {
+        let mut bindings = Bindings::empty();
+        bindings.bind_drag(MouseButton::Left, ALT, DragAction::Tumble);
+        bindings.bind_drag(MouseButton::Middle, ALT, DragAction::Pan);
+        bindings.bind_drag(MouseButton::Right, ALT, DragAction::Zoom);
+
+        bindings.bind_key(Key::Letter('a'), NONE, KeyAction::ResetToDefault);
+        bindings.bind_character('[', KeyAction::TransitionBack);
+        bindings
+    }
+
+    /// 3ds Max's navigation. The middle button pans, tumbles with alt and zooms with control and
+    /// alt. Z goes back to the default view and shift z to the previous view, and the letter keys
+    /// go to the standard views, with u for the isometric user view. In 3ds Max z is zoom extents,
+    /// but the camera doesn't know what is in the scene, so to match it make a view that frames
+    /// the scene with `frame_aabb` the default.
+    pub fn three_ds_max() -> Bindings {
+        let mut bindings = Bindings::empty();
+        bindings.bind_drag(MouseButton::Middle, NONE, DragAction::Pan);
+        bindings.bind_drag(MouseButton::Middle, ALT, DragAction::Tumble);
+        bindings.bind_drag(MouseButton::Middle, CTRL_ALT, DragAction::Zoom);
+
+        bindings.bind_key(Key::Letter('z'), NONE, KeyAction::ResetToDefault);
+        bindings.bind_key(Key::Letter('z'), SHIFT, KeyAction::TransitionBack);
+        let views = [
+            ('f', StandardView::Front),
+            ('k', StandardView::Back),
+            ('l', StandardView::Left),
+            ('t', StandardView::Top),
+            ('b', StandardView::Bottom),
+            ('u', StandardView::Isometric),
+        ];
+        for &(letter, view) in &views {
+            bindings.bind_key(Key::Letter(letter), NONE, KeyAction::StandardView(view));
+        }
+        bindings
+    }
+
+    pub fn bind_drag(&mut self, button: MouseButton, modifiers: Modifiers, action: DragAction) {
+        self.drags.insert((button, modifiers), action);
+    }
+
+    pub fn unbind_drag(&mut self, button: MouseButton, modifiers: Modifiers) {
+        self.drags.remove(&(button, modifiers));
+    }
+
+    pub fn bind_key(&mut self, key: Key, modifiers: Modifiers, action: KeyAction) {
+        self.keys.insert((key, modifiers), action);
+    }
+
+    pub fn unbind_key(&mut self, key: Key, modifiers: Modifiers) {
+        self.keys.remove(&(key, modifiers));
+    }
+
+    /// Bind a typed character, these follow the keyboard layout unlike keys
+    pub fn bind_character(&mut self, c: char, action: KeyAction) {
+        self.characters.insert(c, action);
+    }
+
+    pub fn unbind_character(&mut self, c: char) {
+        self.characters.remove(&c);
+    }
+
+    /// What dragging with the button does while the modifiers are held
+    pub fn drag_action(&self, button: MouseButton, modifiers: Modifiers) -> Option<DragAction> {
+        self.drags
+            .get(&(button, modifiers))
+            .or_else(|| self.drags.get(&(button, NONE)))
+            .cloned()
+    }
+
+    /// What the key does while the modifiers are held
+    pub fn key_action(&self, key: Key, modifiers: Modifiers) -> Option<KeyAction> {
+        if let Some(action) = self.keys.get(&(key, modifiers)) {
+            return Some(*action);
+        }
+
+        let has_modifier_bindings = self
+            .keys
+            .keys()
+            .any(|&(bound, bound_modifiers)| bound == key && bound_modifiers != NONE);
+        if has_modifier_bindings {
+            None
+        } else {
+            self.keys.get(&(key, NONE)).cloned()
+        }
+    }
+
+    pub fn character_action(&self, c: char) -> Option<KeyAction> {
+        self.characters.get(&c).cloned()
+    }
+
+    fn bind_fly_keys(&mut self) {
+        let directions = [
+            ('w', FlyDirection::Forward),
+            ('s', FlyDirection::Backward),
+            ('a', FlyDirection::Left),
+            ('d', FlyDirection::Right),
+            ('e', FlyDirection::Up),
+            ('q', FlyDirection::Down),
+        ];
+        for &(letter, direction) in &directions {
+            self.bind_key(Key::Letter(letter), NONE, KeyAction::Fly(direction));
+        }
+    }
+
+    // The number pad views that Blender uses, with control giving the opposite view
+    fn bind_numpad_views(&mut self) {
+        let views = [
+            (1, StandardView::Front, StandardView::Back),
+            (3, StandardView::Right, StandardView::Left),
+            (7, StandardView::Top, StandardView::Bottom),
+        ];
+        for &(number, view, opposite) in &views {
+            let key = Key::Numpad(number);
+            self.bind_key(key, NONE, KeyAction::StandardView(view));
+            self.bind_key(key, CTRL, KeyAction::StandardView(opposite));
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use camera::{ButtonState, MouseButton, TouchPhase};
+
+mod bindings;
+
+pub use self::bindings::{Bindings, DragAction, KeyAction};
+
+/// The keys that the camera has shortcuts for, independent of the windowing library
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// A letter key, always lowercase
+    Letter(char),
+    /// One of the number keys above the letters
+    Digit(u8),
+    /// One of the number keys on the number pad
+    Numpad(u8),
+}
+
+/// Which modifier keys are held
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+/// An input event for the camera, see `Camera::handle_input`. The adapters for glutin and winit
+/// turn their events into these, and anything else can drive the camera by making them directly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CameraInput {
+    /// The cursor moved to a pixel position, with the origin in the top left of the window
+    CursorMoved { x: f32, y: f32 },
+    /// Relative mouse motion in pixels, which is what looks around while flying
+    MouseMotion { dx: f32, dy: f32 },
+    /// A mouse button was pressed or released
+    MouseButton {
+        button: MouseButton,
+        state: ButtonState,
+    },
+    /// The scroll wheel or touchpad scrolled by a number of pixels
+    Scroll { pixel_delta: f32 },
+    /// The scroll wheel scrolled by a number of lines, or notches
+    ScrollLines { lines: f32 },
+    /// A key was pressed or released
+    Key { key: Key, state: ButtonState },
+    /// A character was typed, the single letter shortcuts use these so they follow the keyboard
+    /// layout
+    Character(char),
+    /// The modifier keys that are held changed
+    Modifiers(Modifiers),
+    /// A finger touched, moved or lifted, at a pixel position like the cursor
+    Touch {
+        id: u64,
+        phase: TouchPhase,
+        x: f32,
+        y: f32,
+    },
+}
@@ -9,16 +9,84 @@ extern crate cgmath;
 extern crate getset;
 #[cfg(feature = "eventhandler")]
 extern crate glutin; // TODO: This could prolly be winit?
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+#[cfg(feature = "winit")]
+extern crate winit;
 
+mod animation;
+mod bounds;
 mod camera;
 #[cfg(feature = "eventhandler")]
 mod eventhandler;
+mod frustum;
+mod input;
+mod path;
 mod perspective;
+mod picking;
+#[cfg(feature = "winit")]
+mod winit_input;
 
+pub use animation::Easing;
+pub use bounds::Aabb;
+pub use bounds::Sphere;
+pub use camera::AxisConstraint;
+pub use camera::BookmarkKey;
 pub use camera::ButtonState;
 pub use camera::Camera;
+pub use camera::CameraState;
+pub use camera::CameraView;
+pub use camera::FlyDirection;
 pub use camera::MouseButton;
+pub use camera::OrbitStyle;
+pub use camera::PlaybackMode;
+pub use camera::StandardView;
+pub use camera::TouchPhase;
+pub use camera::TrackballMapping;
+pub use camera::ZoomMode;
+pub use camera::CAMERA_STATE_VERSION;
 #[cfg(feature = "eventhandler")]
 pub use eventhandler::camera_event_handler;
+#[cfg(feature = "eventhandler")]
+pub use eventhandler::glutin_camera_inputs;
+pub use frustum::Containment;
+pub use frustum::Frustum;
+pub use frustum::Plane;
+pub use input::Bindings;
+pub use input::CameraInput;
+pub use input::DragAction;
+pub use input::Key;
+pub use input::KeyAction;
+pub use input::Modifiers;
+pub use path::CameraPath;
+pub use path::Keyframe;
+pub use perspective::extent_depth_mode_orthographic_transform;
+pub use perspective::extent_orthographic_transform;
+pub use perspective::fov_depth_mode_perspective_transform;
+pub use perspective::fov_infinite_perspective_transform;
+pub use perspective::fov_infinite_reversed_perspective_transform;
 pub use perspective::fov_perspective_transform;
+pub use perspective::fov_reversed_perspective_transform;
+pub use perspective::infinite_perspective_transform;
+pub use perspective::infinite_reversed_perspective_transform;
+pub use perspective::orthographic_transform;
 pub use perspective::perspective_transform;
+pub use perspective::reversed_perspective_transform;
+pub use perspective::ClipSpace;
+pub use perspective::DepthMode;
+pub use perspective::DepthRange;
+pub use perspective::ProjectionMode;
+pub use perspective::YDirection;
+pub use picking::Ray;
+pub use picking::ScreenPoint;
+#[cfg(feature = "winit")]
+pub use winit_input::camera_device_event_handler;
+#[cfg(feature = "winit")]
+pub use winit_input::camera_window_event_handler;
+#[cfg(feature = "winit")]
+pub use winit_input::winit_device_input;
+#[cfg(feature = "winit")]
+pub use winit_input::winit_window_inputs;
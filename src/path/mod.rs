@@ -0,0 +1,262 @@
+use camera::CameraView;
+use cgmath::prelude::*;
+use cgmath::{Quaternion, Vector3};
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A camera view at a point in time, in milliseconds from the start of the path
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Keyframe {
+    pub time: f32,
+    pub view: CameraView,
+}
+
+/// A track of keyframes that the camera can play back with `Camera::play_path`. The target,
+/// distance and field of view follow Catmull-Rom splines, and the rotation follows a squad
+/// spline, so the camera moves smoothly through each keyframe instead of turning sharply at it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CameraPath {
+    keyframes: Vec<Keyframe>,
+}
+
+impl CameraPath {
+    pub fn new() -> CameraPath {
+        CameraPath::default()
+    }
+
+    /// Add a keyframe, the keyframes are kept in time order. A keyframe at the same time as an
+    /// existing one replaces it, and one at a time that is not finite is ignored.
+    pub fn add_keyframe(&mut self, time: f32, view: CameraView) {
+        if !time.is_finite() {
+            return;
+        }
+
+        let keyframe = Keyframe { time, view };
+        match self
+            .keyframes
+            .binary_search_by(|other| other.time.partial_cmp(&time).unwrap())
+        {
+            Ok(index) => self.keyframes[index] = keyframe,
+            Err(index) => self.keyframes.insert(index, keyframe),
+        }
+    }
+
+    /// Remove all the keyframes
+    pub fn clear(&mut self) {
+        self.keyframes.clear();
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    /// The time of the first keyframe
+    pub fn start_time(&self) -> f32 {
+        self.keyframes.first().map_or(0.0, |keyframe| keyframe.time)
+    }
+
+    /// The time of the last keyframe
+    pub fn end_time(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |keyframe| keyframe.time)
+    }
+
+    /// The view at a time along the path, times outside the keyframes hold the first or last
+    /// view. Returns None if there are no keyframes.
+    pub fn sample(&self, time: f32) -> Option<CameraView> {
+        let count = self.keyframes.len();
+        match count {
+            0 => return None,
+            1 => return Some(self.keyframes[0].view),
+            _ => (),
+        }
+        if time <= self.start_time() {
+            return Some(self.keyframes[0].view);
+        }
+        if time >= self.end_time() {
+            return Some(self.keyframes[count - 1].view);
+        }
+
+        // The segment runs from keyframe i to i + 1, with its neighbours shaping the tangents
+        let i = self.keyframes.iter().rposition(|k| k.time <= time).unwrap();
+        let previous = &self.keyframes[i.saturating_sub(1)];
+        let start = &self.keyframes[i];
+        let end = &self.keyframes[i + 1];
+        let next = &self.keyframes[(i + 2).min(count - 1)];
+
+        let span = end.time - start.time;
+        let t = (time - start.time) / span;
+
+        let target = hermite(
+            start.view.target,
+            end.view.target,
+            tangent(previous, end, |view| view.target) * span,
+            tangent(start, next, |view| view.target) * span,
+            t,
+        );
+        let distance = hermite(
+            start.view.distance,
+            end.view.distance,
+            tangent(previous, end, |view| view.distance) * span,
+            tangent(start, next, |view| view.distance) * span,
+            t,
+        );
+        let field_of_view = hermite(
+            start.view.field_of_view,
+            end.view.field_of_view,
+            tangent(previous, end, |view| view.field_of_view) * span,
+            tangent(start, next, |view| view.field_of_view) * span,
+            t,
+        );
+
+        // Squad needs the neighbouring rotations on the same side of the hypersphere, since q and
+        // -q are the same rotation but would send the spline the long way around
+        let q1 = start.view.rotation.normalize();
+        let q0 = same_hemisphere(previous.view.rotation.normalize(), q1);
+        let q2 = same_hemisphere(end.view.rotation.normalize(), q1);
+        let q3 = same_hemisphere(next.view.rotation.normalize(), q2);
+        let rotation = squad(
+            q1,
+            q2,
+            squad_control(q0, q1, q2),
+            squad_control(q1, q2, q3),
+            t,
+        );
+
+        Some(CameraView {
+            target,
+            rotation: rotation.normalize(),
+            distance,
+            field_of_view,
+        })
+    }
+}
+
+// The Catmull-Rom tangent at the keyframe between these two, per millisecond. Dividing by the
+// time between them keeps the speed consistent when keyframes are unevenly spaced, and at the
+// ends of the path one of them is the keyframe itself, which gives a one sided slope.
+fn tangent<T, F>(before: &Keyframe, after: &Keyframe, value: F) -> T
+where
+    T: Copy + Sub<Output = T> + Div<f32, Output = T>,
+    F: Fn(&CameraView) -> T,
+{
+    (value(&after.view) - value(&before.view)) / (after.time - before.time)
+}
+
+// The cubic Hermite spline between two values, with the tangents scaled to the segment
+fn hermite<T>(start: T, end: T, start_tangent: T, end_tangent: T, t: f32) -> T
+where
+    T: Copy + Add<Output = T> + Mul<f32, Output = T>,
+{
+    let t2 = t * t;
+    let t3 = t2 * t;
+    start * (2.0 * t3 - 3.0 * t2 + 1.0)
+        + start_tangent * (t3 - 2.0 * t2 + t)
+        + end * (-2.0 * t3 + 3.0 * t2)
+        + end_tangent * (t3 - t2)
+}
+
+fn same_hemisphere(q: Quaternion<f32>, reference: Quaternion<f32>) -> Quaternion<f32> {
+    if q.dot(reference) < 0.0 {
+        -q
+    } else {
+        q
+    }
+}
+
+// Shoemake's squad, a slerp between the slerp of the keyframes and the slerp of the controls
+fn squad(
+    q1: Quaternion<f32>,
+    q2: Quaternion<f32>,
+    s1: Quaternion<f32>,
+    s2: Quaternion<f32>,
+    t: f32,
+) -> Quaternion<f32> {
+    q1.slerp(q2, t).slerp(s1.slerp(s2, t), 2.0 * t * (1.0 - t))
+}
+
+// The inner control point at q1 that makes the angular velocity continuous through it
+fn squad_control(q0: Quaternion<f32>, q1: Quaternion<f32>, q2: Quaternion<f32>) -> Quaternion<f32> {
+    let inverse = q1.conjugate();
+    let sum = log(inverse * q0) + log(inverse * q2);
+    q1 * exp(sum * -0.25)
+}
+
+// The log of a unit quaternion is a pure quaternion, half the rotation angle along the axis
+fn log(q: Quaternion<f32>) -> Quaternion<f32> {
+    let length = q.v.magnitude();
+    if length < f32::EPSILON {
+        return Quaternion::from_sv(0.0, Vector3::zero());
+    }
+    let angle = length.atan2(q.s);
+    Quaternion::from_sv(0.0, q.v * (angle / length))
+}
+
+fn exp(q: Quaternion<f32>) -> Quaternion<f32> {
+    let angle = q.v.magnitude();
+    if angle < f32::EPSILON {
+        return Quaternion::one();
+    }
+    Quaternion::from_sv(angle.cos(), q.v * (angle.sin() / angle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert::*;
+    use cgmath::Rad;
+
+    fn view(x: f32, angle: f32) -> CameraView {
+        CameraView {
+            target: Vector3::new(x, 0.0, 0.0),
+            rotation: Quaternion::from_angle_y(Rad(angle)),
+            distance: 10.0 + x,
+            field_of_view: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_path_passes_through_keyframes() {
+        let mut path = CameraPath::new();
+        assert!(path.sample(0.0).is_none());
+
+        // Added out of order on purpose
+        path.add_keyframe(2000.0, view(4.0, 1.0));
+        path.add_keyframe(0.0, view(0.0, 0.0));
+        path.add_keyframe(1000.0, view(1.0, 0.5));
+        path.add_keyframe(3000.0, view(9.0, -0.5));
+        path.add_keyframe(f32::NAN, view(0.0, 0.0));
+        assert_eq!(path.keyframes().len(), 4);
+
+        for keyframe in path.keyframes() {
+            let sampled = path.sample(keyframe.time).unwrap();
+            let target: [f32; 3] = sampled.target.into();
+            let expected: [f32; 3] = keyframe.view.target.into();
+            close(&target, &expected, 1e-4);
+            close(
+                sampled.rotation.dot(keyframe.view.rotation).abs(),
+                1.0,
+                1e-4,
+            );
+        }
+        close(path.sample(-10.0).unwrap().distance, 10.0, 1e-5);
+        close(path.sample(5000.0).unwrap().distance, 19.0, 1e-5);
+
+        // A straight line at constant speed stays that way
+        let mut line = CameraPath::new();
+        for i in 0..4 {
+            line.add_keyframe(i as f32 * 100.0, view(i as f32, 0.0));
+        }
+        close(line.sample(150.0).unwrap().target.x, 1.5, 1e-5);
+    }
+
+    #[test]
+    fn test_squad_rotation_is_smooth() {
+        // Turning about one axis at a steady rate, squad keeps the same steady rate
+        let mut path = CameraPath::new();
+        for i in 0..4 {
+            path.add_keyframe(i as f32 * 1000.0, view(0.0, i as f32 * 0.4));
+        }
+        let sampled = path.sample(1500.0).unwrap().rotation;
+        let expected = Quaternion::from_angle_y(Rad(0.6));
+        close(sampled.dot(expected).abs(), 1.0, 1e-5);
+    }
+}
@@ -9,13 +9,67 @@ pub fn perspective_transform(
     right: f32,
     bottom: f32,
     top: f32,
+) -> Matrix4<f32> {
+    let r3c3 = -(far + near) / (far - near);
+    let r3c4 = -(2.0 * far * near) / (far - near);
+    frustum_transform(near, left, right, bottom, top, r3c3, r3c4)
+}
+
+/// Create a perspective transform like `perspective_transform`, except that the depth mapping
+/// is reversed: the near plane maps to 1 and the far plane maps to -1. Paired with a zero to one
+/// depth range, this spreads floating point precision far more evenly across the depth buffer.
+pub fn reversed_perspective_transform(
+    near: f32,
+    far: f32,
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+) -> Matrix4<f32> {
+    let r3c3 = (far + near) / (far - near);
+    let r3c4 = (2.0 * far * near) / (far - near);
+    frustum_transform(near, left, right, bottom, top, r3c3, r3c4)
+}
+
+/// Create a perspective transform like `perspective_transform`, but with the far plane pushed
+/// out to infinity. This is the limit of the standard transform as far goes to infinity.
+pub fn infinite_perspective_transform(
+    near: f32,
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+) -> Matrix4<f32> {
+    frustum_transform(near, left, right, bottom, top, -1.0, -2.0 * near)
+}
+
+/// Create a perspective transform with both a reversed depth mapping and a far plane at
+/// infinity. The near plane maps to 1, and points infinitely far away map to -1.
+pub fn infinite_reversed_perspective_transform(
+    near: f32,
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+) -> Matrix4<f32> {
+    frustum_transform(near, left, right, bottom, top, 1.0, 2.0 * near)
+}
+
+// All the perspective variants share the same x and y mapping, they only differ in how the eye
+// space z coordinate gets mapped to clip space depth.
+fn frustum_transform(
+    near: f32,
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+    r3c3: f32,
+    r3c4: f32,
 ) -> Matrix4<f32> {
     let r1c1 = (2.0 * near) / (right - left);
     let r1c3 = (right + left) / (right - left);
     let r2c2 = (2.0 * near) / (top - bottom);
     let r2c3 = (top + bottom) / (top - bottom);
-    let r3c3 = -(far + near) / (far - near);
-    let r3c4 = -(2.0 * far * near) / (far - near);
 
     #[cfg_attr(rustfmt, rustfmt_skip)]
     Matrix4::new(
@@ -35,11 +89,272 @@ pub fn fov_perspective_transform(
     near: f32,
     far: f32,
 ) -> Matrix4<f32> {
+    let (left, right, bottom, top) = fov_near_plane(field_of_view, aspect_ratio, near);
+    perspective_transform(near, far, left, right, bottom, top)
+}
+
+/// The field of view version of `reversed_perspective_transform`
+pub fn fov_reversed_perspective_transform(
+    field_of_view: f32,
+    aspect_ratio: f32,
+    near: f32,
+    far: f32,
+) -> Matrix4<f32> {
+    let (left, right, bottom, top) = fov_near_plane(field_of_view, aspect_ratio, near);
+    reversed_perspective_transform(near, far, left, right, bottom, top)
+}
+
+/// The field of view version of `infinite_perspective_transform`
+pub fn fov_infinite_perspective_transform(
+    field_of_view: f32,
+    aspect_ratio: f32,
+    near: f32,
+) -> Matrix4<f32> {
+    let (left, right, bottom, top) = fov_near_plane(field_of_view, aspect_ratio, near);
+    infinite_perspective_transform(near, left, right, bottom, top)
+}
+
+/// The field of view version of `infinite_reversed_perspective_transform`
+pub fn fov_infinite_reversed_perspective_transform(
+    field_of_view: f32,
+    aspect_ratio: f32,
+    near: f32,
+) -> Matrix4<f32> {
+    let (left, right, bottom, top) = fov_near_plane(field_of_view, aspect_ratio, near);
+    infinite_reversed_perspective_transform(near, left, right, bottom, top)
+}
+
+// Find the left, right, bottom and top of a near plane centered on the viewing direction
+fn fov_near_plane(field_of_view: f32, aspect_ratio: f32, near: f32) -> (f32, f32, f32, f32) {
     let top = near * (field_of_view / 2.0).tan();
     let bottom = -top;
     let right = top * aspect_ratio;
     let left = -right;
-    perspective_transform(near, far, left, right, bottom, top)
+    (left, right, bottom, top)
+}
+
+/// How eye space depth is mapped into clip space depth. Reversed mappings put the near plane at
+/// the far end of the depth range, and infinite mappings have no far plane at all.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DepthMode {
+    Standard,
+    Reversed,
+    Infinite,
+    InfiniteReversed,
+}
+
+/// The range that normalized device depth lands in once the perspective divide is done
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DepthRange {
+    /// Depth in [-1, 1], as used by OpenGL
+    NegativeOneToOne,
+
+    /// Depth in [0, 1], as used by Vulkan, Direct3D, Metal and wgpu
+    ZeroToOne,
+}
+
+/// Which way the y-axis of normalized device coordinates points on screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum YDirection {
+    Up,
+    Down,
+}
+
+/// A clip space convention describes what a graphics backend expects the output of the vertex
+/// shader to look like. All the transforms in this module natively produce OpenGL clip space,
+/// the clip space convention is applied on top of that.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClipSpace {
+    pub depth_range: DepthRange,
+    pub y_direction: YDirection,
+}
+
+impl ClipSpace {
+    /// Depth in [-1, 1] with the y-axis pointing up
+    pub fn opengl() -> ClipSpace {
+        ClipSpace {
+            depth_range: DepthRange::NegativeOneToOne,
+            y_direction: YDirection::Up,
+        }
+    }
+
+    /// Depth in [0, 1] with the y-axis pointing down
+    pub fn vulkan() -> ClipSpace {
+        ClipSpace {
+            depth_range: DepthRange::ZeroToOne,
+            y_direction: YDirection::Down,
+        }
+    }
+
+    /// Depth in [0, 1] with the y-axis pointing up
+    pub fn direct3d() -> ClipSpace {
+        ClipSpace {
+            depth_range: DepthRange::ZeroToOne,
+            y_direction: YDirection::Up,
+        }
+    }
+
+    /// Depth in [0, 1] with the y-axis pointing up
+    pub fn metal() -> ClipSpace {
+        ClipSpace::direct3d()
+    }
+
+    /// Depth in [0, 1] with the y-axis pointing up
+    pub fn wgpu() -> ClipSpace {
+        ClipSpace::direct3d()
+    }
+
+    /// The transform that takes OpenGL clip space coordinates into this clip space. Note that
+    /// flipping the y-axis also flips the winding order of triangles.
+    #[rustfmt::skip]
+    pub fn opengl_to_clip_transform(&self) -> Matrix4<f32> {
+        let r2c2 = match self.y_direction {
+            YDirection::Up => 1.0,
+            YDirection::Down => -1.0,
+        };
+
+        // Remapping depth has to happen before the perspective divide, so we blend in w
+        let (r3c3, r3c4) = match self.depth_range {
+            DepthRange::NegativeOneToOne => (1.0, 0.0),
+            DepthRange::ZeroToOne => (0.5, 0.5),
+        };
+
+        Matrix4::new(
+            1.0, 0.0,  0.0,  0.0,
+            0.0, r2c2, 0.0,  0.0,
+            0.0, 0.0,  r3c3, 0.0,
+            0.0, 0.0,  r3c4, 1.0
+        )
+    }
+
+    /// Convert a transform that outputs OpenGL clip space into one that outputs this clip space
+    pub fn convert(&self, opengl_transform: Matrix4<f32>) -> Matrix4<f32> {
+        self.opengl_to_clip_transform() * opengl_transform
+    }
+
+    /// The normalized device depths of the near and far planes for a given depth mode. For the
+    /// infinite modes the far depth is where points infinitely far away end up.
+    pub fn near_far_depths(&self, depth_mode: DepthMode) -> (f32, f32) {
+        let (min, max) = match self.depth_range {
+            DepthRange::NegativeOneToOne => (-1.0, 1.0),
+            DepthRange::ZeroToOne => (0.0, 1.0),
+        };
+        match depth_mode {
+            DepthMode::Standard | DepthMode::Infinite => (min, max),
+            DepthMode::Reversed | DepthMode::InfiniteReversed => (max, min),
+        }
+    }
+}
+
+impl Default for ClipSpace {
+    fn default() -> ClipSpace {
+        ClipSpace::opengl()
+    }
+}
+
+/// Create the perspective transform that matches the given depth mode and clip space
+/// convention. The far distance is ignored by the infinite modes.
+pub fn fov_depth_mode_perspective_transform(
+    clip_space: ClipSpace,
+    depth_mode: DepthMode,
+    field_of_view: f32,
+    aspect_ratio: f32,
+    near: f32,
+    far: f32,
+) -> Matrix4<f32> {
+    let opengl_transform = match depth_mode {
+        DepthMode::Standard => fov_perspective_transform(field_of_view, aspect_ratio, near, far),
+        DepthMode::Reversed => {
+            fov_reversed_perspective_transform(field_of_view, aspect_ratio, near, far)
+        }
+        DepthMode::Infinite => {
+            fov_infinite_perspective_transform(field_of_view, aspect_ratio, near)
+        }
+        DepthMode::InfiniteReversed => {
+            fov_infinite_reversed_perspective_transform(field_of_view, aspect_ratio, near)
+        }
+    };
+    clip_space.convert(opengl_transform)
+}
+
+/// Create an orthographic transform that takes eye space coordinates into clip space
+/// using the same box specification as `perspective_transform`. Unlike the perspective
+/// transform, the left, right, bottom and top values are measured in world units and are
+/// not scaled by the distance to the near plane.
+#[rustfmt::skip]
+pub fn orthographic_transform(
+    near: f32,
+    far: f32,
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+) -> Matrix4<f32> {
+    let r1c1 = 2.0 / (right - left);
+    let r1c4 = -(right + left) / (right - left);
+    let r2c2 = 2.0 / (top - bottom);
+    let r2c4 = -(top + bottom) / (top - bottom);
+    let r3c3 = -2.0 / (far - near);
+    let r3c4 = -(far + near) / (far - near);
+
+    Matrix4::new(
+        r1c1, 0.0,  0.0,  0.0,
+        0.0,  r2c2, 0.0,  0.0,
+        0.0,  0.0,  r3c3, 0.0,
+        r1c4, r2c4, r3c4, 1.0
+    )
+}
+
+/// Create an orthographic transform where the view volume is centered on the viewing
+/// direction. The extent is half of the height of the view volume in world units, the width
+/// follows from the aspect ratio.
+pub fn extent_orthographic_transform(
+    extent: f32,
+    aspect_ratio: f32,
+    near: f32,
+    far: f32,
+) -> Matrix4<f32> {
+    let top = extent;
+    let bottom = -top;
+    let right = top * aspect_ratio;
+    let left = -right;
+    orthographic_transform(near, far, left, right, bottom, top)
+}
+
+/// Create the orthographic transform that matches the given depth mode and clip space
+/// convention. Without a perspective divide there is no way to push the far plane out, so the
+/// infinite modes still use the far distance and only the reversal is honored.
+pub fn extent_depth_mode_orthographic_transform(
+    clip_space: ClipSpace,
+    depth_mode: DepthMode,
+    extent: f32,
+    aspect_ratio: f32,
+    near: f32,
+    far: f32,
+) -> Matrix4<f32> {
+    let opengl_transform = match depth_mode {
+        DepthMode::Standard | DepthMode::Infinite => {
+            extent_orthographic_transform(extent, aspect_ratio, near, far)
+        }
+        // Swapping the planes flips which end of the depth range each one lands on
+        DepthMode::Reversed | DepthMode::InfiniteReversed => {
+            extent_orthographic_transform(extent, aspect_ratio, far, near)
+        }
+    };
+    clip_space.convert(opengl_transform)
+}
+
+/// The camera can either use a perspective projection, where distant objects appear smaller,
+/// or an orthographic projection, where objects keep their size regardless of distance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ProjectionMode {
+    Perspective,
+    Orthographic,
 }
 
 /// The near plane distance is dependent on the field of view. It is useful to have this
@@ -47,3 +362,93 @@ pub fn fov_perspective_transform(
 pub fn fov_near_distance(field_of_view: f32) -> f32 {
     1.0 / (field_of_view / 2.0).tan()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert::*;
+    use cgmath::Vector4;
+
+    // Project an eye space point on the viewing axis and return its normalized depth
+    fn ndc_depth(transform: Matrix4<f32>, eye_z: f32) -> f32 {
+        let clip = transform * Vector4::new(0.0, 0.0, eye_z, 1.0);
+        clip.z / clip.w
+    }
+
+    #[test]
+    fn test_depth_mode_planes() {
+        let (near, far) = (0.5, 100.0);
+        let standard = fov_perspective_transform(1.0, 1.5, near, far);
+        close(ndc_depth(standard, -near), -1.0, 1e-5);
+        close(ndc_depth(standard, -far), 1.0, 1e-5);
+
+        let reversed = fov_reversed_perspective_transform(1.0, 1.5, near, far);
+        close(ndc_depth(reversed, -near), 1.0, 1e-5);
+        close(ndc_depth(reversed, -far), -1.0, 1e-5);
+
+        let infinite = fov_infinite_perspective_transform(1.0, 1.5, near);
+        close(ndc_depth(infinite, -near), -1.0, 1e-5);
+        close(ndc_depth(infinite, -1.0e7), 1.0, 1e-5);
+
+        let infinite_reversed = fov_infinite_reversed_perspective_transform(1.0, 1.5, near);
+        close(ndc_depth(infinite_reversed, -near), 1.0, 1e-5);
+        close(ndc_depth(infinite_reversed, -1.0e7), -1.0, 1e-5);
+    }
+
+    #[test]
+    fn test_orthographic_planes() {
+        let (near, far) = (0.5, 100.0);
+        let standard = extent_depth_mode_orthographic_transform(
+            ClipSpace::opengl(),
+            DepthMode::Standard,
+            2.0,
+            1.0,
+            near,
+            far,
+        );
+        close(ndc_depth(standard, -near), -1.0, 1e-5);
+        close(ndc_depth(standard, -far), 1.0, 1e-5);
+
+        let reversed = extent_depth_mode_orthographic_transform(
+            ClipSpace::opengl(),
+            DepthMode::Reversed,
+            2.0,
+            1.0,
+            near,
+            far,
+        );
+        close(ndc_depth(reversed, -near), 1.0, 1e-5);
+        close(ndc_depth(reversed, -far), -1.0, 1e-5);
+    }
+
+    #[test]
+    fn test_clip_space_conversion() {
+        let (near, far) = (0.5, 100.0);
+        let vulkan = fov_depth_mode_perspective_transform(
+            ClipSpace::vulkan(),
+            DepthMode::Standard,
+            1.0,
+            1.0,
+            near,
+            far,
+        );
+        close(ndc_depth(vulkan, -near), 0.0, 1e-5);
+        close(ndc_depth(vulkan, -far), 1.0, 1e-5);
+
+        // Vulkan's y-axis points down, so a point above the viewing axis lands at negative y
+        let clip = vulkan * Vector4::new(0.0, 1.0, -10.0, 1.0);
+        assert!(clip.y / clip.w < 0.0);
+
+        // Reversed infinite depth in zero to one should be exactly near / distance
+        let reversed = fov_depth_mode_perspective_transform(
+            ClipSpace::wgpu(),
+            DepthMode::InfiniteReversed,
+            1.0,
+            1.0,
+            near,
+            far,
+        );
+        close(ndc_depth(reversed, -near), 1.0, 1e-6);
+        close(ndc_depth(reversed, -50.0), near / 50.0, 1e-6);
+    }
+}
@@ -0,0 +1,87 @@
+use cgmath::prelude::*;
+use cgmath::{Matrix4, Vector2, Vector3};
+
+/// A ray in world coordinates, useful for picking objects under the mouse
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    /// Where the ray starts, for rays made by unprojecting this is on the near plane
+    pub origin: Vector3<f32>,
+
+    /// The unit length direction the ray travels in
+    pub direction: Vector3<f32>,
+}
+
+/// Where a world space point ends up on the screen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScreenPoint {
+    /// Pixel coordinates with the origin in the top left of the window
+    pub pixel: Vector2<f32>,
+
+    /// The normalized device depth of the point, using the camera's depth mode and clip space
+    pub depth: f32,
+
+    /// Points behind the camera still project somewhere, but that place is meaningless. Check
+    /// this before drawing anything at the pixel.
+    pub in_front: bool,
+}
+
+impl Ray {
+    /// Find the point that is a given distance along the ray
+    pub fn at(&self, distance: f32) -> Vector3<f32> {
+        self.origin + self.direction * distance
+    }
+
+    /// Turn a point in normalized device coordinates into a world space ray by running it
+    /// backwards through a world to clip space transform. This works for any transform, including
+    /// off center frustums from `perspective_transform`. The near and far depths are the
+    /// normalized device depths of the near and far planes, for example -1 and 1 for the standard
+    /// OpenGL depth mapping. Returns None if the transform cannot be inverted.
+    pub fn unproject(
+        clipspace_transform: Matrix4<f32>,
+        ndc: Vector2<f32>,
+        near_depth: f32,
+        far_depth: f32,
+    ) -> Option<Ray> {
+        let inverse = clipspace_transform.invert()?;
+
+        // The far plane may be infinitely far away, which would put it at w = 0 after
+        // unprojecting. Halfway between the planes is always a finite point though, and any
+        // point past the near plane will do for finding the direction.
+        let middle_depth = (near_depth + far_depth) / 2.0;
+        let near_point = inverse * ndc.extend(near_depth).extend(1.0);
+        let middle_point = inverse * ndc.extend(middle_depth).extend(1.0);
+        let near_point = near_point.truncate() / near_point.w;
+        let middle_point = middle_point.truncate() / middle_point.w;
+
+        Some(Ray {
+            origin: near_point,
+            direction: (middle_point - near_point).normalize(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert::*;
+    use perspective::perspective_transform;
+
+    #[test]
+    fn test_unproject_off_center_frustum() {
+        let transform = perspective_transform(1.0, 100.0, -0.5, 2.0, -1.0, 0.25);
+
+        // Any point in the frustum should lie on the ray through its own projection
+        let point = Vector3::new(3.0, -2.0, -20.0);
+        let clip = transform * point.extend(1.0);
+        let ndc = Vector2::new(clip.x / clip.w, clip.y / clip.w);
+
+        let ray = Ray::unproject(transform, ndc, -1.0, 1.0).unwrap();
+        let distance = (point - ray.origin).dot(ray.direction);
+        let closest: [f32; 3] = ray.at(distance).into();
+        close(&closest, &[3.0, -2.0, -20.0], 1e-3);
+
+        // Rays start on the near plane and head away from the eye
+        close(ray.origin.z, -1.0, 1e-5);
+        assert!(ray.direction.z < 0.0);
+    }
+}
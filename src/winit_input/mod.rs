@@ -0,0 +1,175 @@
+use camera::*;
+use input::{CameraInput, Key, Modifiers};
+use winit;
+use winit::event::{DeviceEvent, ElementState, MouseScrollDelta, WindowEvent};
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+/// The suggested handling of winit window events, for `ApplicationHandler::window_event`
+pub fn camera_window_event_handler(cam: &mut Camera, event: &WindowEvent) {
+    for input in winit_window_inputs(event) {
+        cam.handle_input(input);
+    }
+}
+
+/// The suggested handling of winit device events, for `ApplicationHandler::device_event`
+pub fn camera_device_event_handler(cam: &mut Camera, event: &DeviceEvent) {
+    if let Some(input) = winit_device_input(event) {
+        cam.handle_input(input);
+    }
+}
+
+/// Turn a winit window event into camera inputs. Key presses come with the text they typed, so
+/// one event can give both a key and characters.
+pub fn winit_window_inputs(event: &WindowEvent) -> Vec<CameraInput> {
+    let mut inputs = Vec::new();
+    match *event {
+        WindowEvent::CursorMoved { position, .. } => {
+            inputs.push(CameraInput::CursorMoved {
+                x: position.x as f32,
+                y: position.y as f32,
+            });
+        }
+        WindowEvent::MouseInput { state, button, .. } => {
+            let button = match button {
+                winit::event::MouseButton::Left => MouseButton::Left,
+                winit::event::MouseButton::Right => MouseButton::Right,
+                winit::event::MouseButton::Middle => MouseButton::Middle,
+                winit::event::MouseButton::Back => MouseButton::Back,
+                winit::event::MouseButton::Forward => MouseButton::Forward,
+                winit::event::MouseButton::Other(number) => MouseButton::Other(number),
+            };
+            inputs.push(CameraInput::MouseButton {
+                button,
+                state: winit_button_state(state),
+            });
+        }
+        WindowEvent::MouseWheel {
+            delta: MouseScrollDelta::PixelDelta(position),
+            ..
+        } => {
+            inputs.push(CameraInput::Scroll {
+                pixel_delta: position.y as f32,
+            });
+        }
+        WindowEvent::MouseWheel {
+            delta: MouseScrollDelta::LineDelta(_, y),
+            ..
+        } => {
+            inputs.push(CameraInput::ScrollLines { lines: y });
+        }
+        WindowEvent::Touch(winit::event::Touch {
+            phase,
+            location,
+            id,
+            ..
+        }) => {
+            let phase = match phase {
+                winit::event::TouchPhase::Started => TouchPhase::Started,
+                winit::event::TouchPhase::Moved => TouchPhase::Moved,
+                winit::event::TouchPhase::Ended => TouchPhase::Ended,
+                winit::event::TouchPhase::Cancelled => TouchPhase::Cancelled,
+            };
+            inputs.push(CameraInput::Touch {
+                id,
+                phase,
+                x: location.x as f32,
+                y: location.y as f32,
+            });
+        }
+        WindowEvent::ModifiersChanged(modifiers) => {
+            let state = modifiers.state();
+            inputs.push(CameraInput::Modifiers(Modifiers {
+                shift: state.shift_key(),
+                ctrl: state.control_key(),
+                alt: state.alt_key(),
+                logo: state.super_key(),
+            }));
+        }
+        WindowEvent::KeyboardInput { ref event, .. } => {
+            if let PhysicalKey::Code(code) = event.physical_key {
+                if let Some(key) = winit_key(code) {
+                    inputs.push(CameraInput::Key {
+                        key,
+                        state: winit_button_state(event.state),
+                    });
+                }
+            }
+            if let Some(ref text) = event.text {
+                inputs.extend(text.chars().map(CameraInput::Character));
+            }
+        }
+        _ => (),
+    }
+    inputs
+}
+
+/// Turn a winit device event into a camera input
+pub fn winit_device_input(event: &DeviceEvent) -> Option<CameraInput> {
+    match *event {
+        DeviceEvent::MouseMotion { delta: (x, y) } => Some(CameraInput::MouseMotion {
+            dx: x as f32,
+            dy: y as f32,
+        }),
+        _ => None,
+    }
+}
+
+fn winit_button_state(state: ElementState) -> ButtonState {
+    match state {
+        ElementState::Pressed => ButtonState::Pressed,
+        ElementState::Released => ButtonState::Released,
+    }
+}
+
+fn winit_key(code: KeyCode) -> Option<Key> {
+    let key = match code {
+        KeyCode::KeyA => Key::Letter('a'),
+        KeyCode::KeyB => Key::Letter('b'),
+        KeyCode::KeyC => Key::Letter('c'),
+        KeyCode::KeyD => Key::Letter('d'),
+        KeyCode::KeyE => Key::Letter('e'),
+        KeyCode::KeyF => Key::Letter('f'),
+        KeyCode::KeyG => Key::Letter('g'),
+        KeyCode::KeyH => Key::Letter('h'),
+        KeyCode::KeyI => Key::Letter('i'),
+        KeyCode::KeyJ => Key::Letter('j'),
+        KeyCode::KeyK => Key::Letter('k'),
+        KeyCode::KeyL => Key::Letter('l'),
+        KeyCode::KeyM => Key::Letter('m'),
+        KeyCode::KeyN => Key::Letter('n'),
+        KeyCode::KeyO => Key::Letter('o'),
+        KeyCode::KeyP => Key::Letter('p'),
+        KeyCode::KeyQ => Key::Letter('q'),
+        KeyCode::KeyR => Key::Letter('r'),
+        KeyCode::KeyS => Key::Letter('s'),
+        KeyCode::KeyT => Key::Letter('t'),
+        KeyCode::KeyU => Key::Letter('u'),
+        KeyCode::KeyV => Key::Letter('v'),
+        KeyCode::KeyW => Key::Letter('w'),
+        KeyCode::KeyX => Key::Letter('x'),
+        KeyCode::KeyY => Key::Letter('y'),
+        KeyCode::KeyZ => Key::Letter('z'),
+        KeyCode::Digit0 => Key::Digit(0),
+        KeyCode::Digit1 => Key::Digit(1),
+        KeyCode::Digit2 => Key::Digit(2),
+        KeyCode::Digit3 => Key::Digit(3),
+        KeyCode::Digit4 => Key::Digit(4),
+        KeyCode::Digit5 => Key::Digit(5),
+        KeyCode::Digit6 => Key::Digit(6),
+        KeyCode::Digit7 => Key::Digit(7),
+        KeyCode::Digit8 => Key::Digit(8),
+        KeyCode::Digit9 => Key::Digit(9),
+        KeyCode::Numpad0 => Key::Numpad(0),
+        KeyCode::Numpad1 => Key::Numpad(1),
+        KeyCode::Numpad2 => Key::Numpad(2),
+        KeyCode::Numpad3 => Key::Numpad(3),
+        KeyCode::Numpad4 => Key::Numpad(4),
+        KeyCode::Numpad5 => Key::Numpad(5),
+        KeyCode::Numpad6 => Key::Numpad(6),
+        KeyCode::Numpad7 => Key::Numpad(7),
+        KeyCode::Numpad8 => Key::Numpad(8),
+        KeyCode::Numpad9 => Key::Numpad(9),
+        _ => return None,
+    };
+    Some(key)
+}